@@ -0,0 +1,41 @@
+from typing import Any
+
+
+class Branch:
+    """
+    A local or remote-tracking branch.
+    """
+
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, ...]: ...
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the fields as a plain dictionary, e.g. for `pandas.DataFrame`."""
+        ...
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "Branch":
+        """Rebuild an instance from the output of `to_dict`."""
+        ...
+
+    def __init__(self, name: str, is_remote: bool):
+        """
+        Create a branch description.
+
+        Parameters:
+            name (str): The branch name.
+            is_remote (bool): Whether the branch is a remote-tracking branch.
+        """
+        ...
+
+    @property
+    def name(self) -> str:
+        """The branch name."""
+        ...
+
+    @property
+    def is_remote(self) -> bool:
+        """True if the branch is a remote-tracking branch."""
+        ...
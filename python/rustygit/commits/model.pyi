@@ -1,8 +1,29 @@
+from datetime import datetime
+from typing import Any, ClassVar, Optional
+
+from ..repo import Repo
+from ..tree.model import TreeEntry
+
+
 class Commit:
     """
     Represents a Git commit, providing detailed metadata about each change in the repository.
     """
 
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, ...]: ...
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the fields as a plain dictionary, e.g. for `pandas.DataFrame`."""
+        ...
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "Commit":
+        """Rebuild an instance from the output of `to_dict`."""
+        ...
+
     @property
     def hash(self) -> str:
         """SHA-1 hash of the commit."""
@@ -10,12 +31,12 @@ class Commit:
 
     @property
     def author(self) -> str:
-        """Name of the original author of the commit."""
+        """Name of the original author of the commit, canonicalized through the mailmap."""
         ...
 
     @property
     def author_email(self) -> str:
-        """Email address of the original author of the commit."""
+        """Email address of the original author of the commit, canonicalized through the mailmap."""
         ...
 
     @property
@@ -23,14 +44,24 @@ class Commit:
         """Timestamp (in seconds since epoch) when the commit was authored."""
         ...
 
+    @property
+    def author_offset_minutes(self) -> int:
+        """The author's UTC offset, in minutes, when the commit was authored."""
+        ...
+
+    @property
+    def author_datetime(self) -> datetime:
+        """Timezone-aware datetime when the commit was authored, in the author's UTC offset."""
+        ...
+
     @property
     def committer(self) -> str:
-        """Name of the person who committed the change (may differ from the author)."""
+        """Name of the person who committed the change (may differ from the author), canonicalized through the mailmap."""
         ...
 
     @property
     def committer_email(self) -> str:
-        """Email address of the committer."""
+        """Email address of the committer, canonicalized through the mailmap."""
         ...
 
     @property
@@ -38,6 +69,16 @@ class Commit:
         """Timestamp (in seconds since epoch) when the commit was applied to the repository."""
         ...
 
+    @property
+    def commit_offset_minutes(self) -> int:
+        """The committer's UTC offset, in minutes, when the commit was applied."""
+        ...
+
+    @property
+    def commit_datetime(self) -> datetime:
+        """Timezone-aware datetime when the commit was applied, in the committer's UTC offset."""
+        ...
+
     @property
     def message(self) -> str:
         """The full commit message."""
@@ -48,13 +89,245 @@ class Commit:
         """List of parent commit hashes (used for merges and history tracking)."""
         ...
 
+    @property
+    def raw_author(self) -> str:
+        """Author name exactly as recorded in the commit, before mailmap resolution."""
+        ...
+
+    @property
+    def raw_author_email(self) -> str:
+        """Author email exactly as recorded in the commit, before mailmap resolution."""
+        ...
+
+    @property
+    def raw_committer(self) -> str:
+        """Committer name exactly as recorded in the commit, before mailmap resolution."""
+        ...
+
+    @property
+    def raw_committer_email(self) -> str:
+        """Committer email exactly as recorded in the commit, before mailmap resolution."""
+        ...
+
+    @property
+    def co_authors(self) -> list[Identity]:
+        """Identities from `Co-authored-by` trailers, canonicalized through the mailmap."""
+        ...
+
+    @property
+    def subject(self) -> str:
+        """First paragraph of the message, with line breaks folded into spaces (like `%s`)."""
+        ...
+
+    @property
+    def body(self) -> str:
+        """The message after the subject paragraph, including any trailers (like `%b`)."""
+        ...
+
+    @property
+    def trailers(self) -> list[Trailer]:
+        """Trailers from the final paragraph of the message, parsed using git's trailer rules."""
+        ...
+
+    @property
+    def conventional(self) -> Optional[ConventionalCommit]:
+        """The Conventional Commits parse of the subject, or None if it does not follow the format."""
+        ...
+
+    @property
+    def tree_id(self) -> str:
+        """SHA of the commit's root tree."""
+        ...
+
+    @property
+    def encoding(self) -> Optional[str]:
+        """The message encoding from the commit header, or None for the default UTF-8."""
+        ...
+
+    @property
+    def is_signed(self) -> bool:
+        """True if the commit carries a GPG, SSH or X.509 signature."""
+        ...
+
+    @property
+    def signature_format(self) -> Optional[str]:
+        """Kind of signature: "openpgp", "ssh" or "x509", or None if unsigned."""
+        ...
+
+    @property
+    def raw_header(self) -> str:
+        """The commit object's header exactly as stored (tree, parents, author, committer, ...)."""
+        ...
+
+    def parent_commits(self, repo: Repo, mailmap: Optional[str] = None) -> list[Commit]:
+        """
+        Load the parent commits.
+
+        Named `parent_commits` rather than `parents` because `parents` is
+        already the property holding the parent hashes.
+
+        Parameters:
+            repo (Repo): The repository the commit belongs to.
+            mailmap (Optional[str]): Mailmap text to use instead of the repository's
+                `.mailmap` and `mailmap.file` configuration.
+
+        Returns:
+            list[Commit]: The parent commits, in order.
+        """
+        ...
+
+    def tree(self, repo: Repo, recursive: bool = False) -> list[TreeEntry]:
+        """
+        List the entries of the commit's root tree.
+
+        Parameters:
+            repo (Repo): The repository the commit belongs to.
+            recursive (bool): Descend into subdirectories and list only their files, like `git ls-tree -r`.
+
+        Returns:
+            list[TreeEntry]: The tree entries.
+        """
+        ...
+
+    def diff_to_parent(self, repo: Repo, parent: int = 0) -> list[DiffEntry]:
+        """
+        Summarize the changes introduced by this commit relative to one of its parents.
+
+        Root commits are compared with the empty tree.
+
+        Parameters:
+            repo (Repo): The repository the commit belongs to.
+            parent (int): Index of the parent to compare against. Defaults to the first parent.
+
+        Returns:
+            list[DiffEntry]: Per-file line additions and deletions.
+        """
+        ...
+
+    def files_changed(self, repo: Repo) -> list[str]:
+        """
+        List the paths changed relative to the first parent.
+
+        Parameters:
+            repo (Repo): The repository the commit belongs to.
+
+        Returns:
+            list[str]: The changed paths.
+        """
+        ...
+
+
+class Identity:
+    """
+    A name and email pair, such as a co-author.
+    """
+
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, ...]: ...
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the fields as a plain dictionary, e.g. for `pandas.DataFrame`."""
+        ...
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "Identity":
+        """Rebuild an instance from the output of `to_dict`."""
+        ...
+
+    @property
+    def name(self) -> str:
+        """The person's name."""
+        ...
+
+    @property
+    def email(self) -> str:
+        """The person's email address."""
+        ...
+
+
+class Trailer:
+    """
+    A `Key: value` trailer from the end of a commit message, e.g. `Signed-off-by`.
+    """
+
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, ...]: ...
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the fields as a plain dictionary, e.g. for `pandas.DataFrame`."""
+        ...
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "Trailer":
+        """Rebuild an instance from the output of `to_dict`."""
+        ...
+
+    @property
+    def key(self) -> str:
+        """The trailer key as written, e.g. "Reviewed-by"."""
+        ...
+
+    @property
+    def value(self) -> str:
+        """The trailer value, with continuation lines joined by spaces."""
+        ...
+
+
+class ConventionalCommit:
+    """
+    A Conventional Commits header: `type(scope)!: description`.
+    """
+
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, ...]: ...
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the fields as a plain dictionary, e.g. for `pandas.DataFrame`."""
+        ...
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "ConventionalCommit":
+        """Rebuild an instance from the output of `to_dict`."""
+        ...
+
+    @property
+    def type(self) -> str:
+        """The commit type, e.g. "feat" or "fix"."""
+        ...
+
+    @property
+    def scope(self) -> Optional[str]:
+        """The scope in parentheses, if any."""
+        ...
+
+    @property
+    def breaking(self) -> bool:
+        """True if the header has `!` or the body has a `BREAKING CHANGE:` footer."""
+        ...
 
-def get_commit_history(path: str) -> list[Commit]:
+    @property
+    def description(self) -> str:
+        """The text after the colon."""
+        ...
+
+
+def get_commit_history(path: str, mailmap: Optional[str] = None) -> list[Commit]:
     """
     Retrieve the complete commit history of a Git repository.
 
+    Author and committer identities are canonicalized through a mailmap; the
+    values as recorded remain available in the `raw_*` properties.
+
     Parameters:
         path (str): Path to the local Git repository.
+        mailmap (Optional[str]): Mailmap text to use instead of the repository's
+            `.mailmap` and `mailmap.file` configuration.
 
     Returns:
         list[Commit]: A list of Commit objects sorted by reverse chronological order (most recent first).
@@ -70,6 +343,20 @@ class DiffEntry:
     Represents a summary of file changes between two commits.
     """
 
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, ...]: ...
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the fields as a plain dictionary, e.g. for `pandas.DataFrame`."""
+        ...
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "DiffEntry":
+        """Rebuild an instance from the output of `to_dict`."""
+        ...
+
     @property
     def path(self) -> str:
         """The relative path of the file that changed."""
@@ -86,6 +373,61 @@ class DiffEntry:
         ...
 
 
+class DiffHunk:
+    """
+    One hunk of a textual diff.
+    """
+
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, ...]: ...
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the fields as a plain dictionary, e.g. for `pandas.DataFrame`."""
+        ...
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "DiffHunk":
+        """Rebuild an instance from the output of `to_dict`."""
+        ...
+
+    @property
+    def path(self) -> str:
+        """The relative path of the file the hunk belongs to."""
+        ...
+
+    @property
+    def header(self) -> str:
+        """The `@@ -a,b +c,d @@` header line, including any function context."""
+        ...
+
+    @property
+    def old_start(self) -> int:
+        """First line of the hunk in the old file."""
+        ...
+
+    @property
+    def old_lines(self) -> int:
+        """Number of lines of the old file covered by the hunk."""
+        ...
+
+    @property
+    def new_start(self) -> int:
+        """First line of the hunk in the new file."""
+        ...
+
+    @property
+    def new_lines(self) -> int:
+        """Number of lines of the new file covered by the hunk."""
+        ...
+
+    @property
+    def lines(self) -> list[str]:
+        """Lines of the hunk prefixed with "+", "-" or " ", without line endings."""
+        ...
+
+
 def get_file_change_summary(path: str, commit1: str, commit2: str) -> list[DiffEntry]:
     """
     Compare two commits and return a summary of file-level changes (line additions and deletions).
@@ -111,6 +453,20 @@ class BlameLine:
     Provides metadata about who last modified a line, when it was changed, and in which commit.
     """
 
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, ...]: ...
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the fields as a plain dictionary, e.g. for `pandas.DataFrame`."""
+        ...
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "BlameLine":
+        """Rebuild an instance from the output of `to_dict`."""
+        ...
+
     @property
     def line_number(self) -> int:
         """The 1-based line number in the file."""
@@ -128,12 +484,12 @@ class BlameLine:
 
     @property
     def author(self) -> str:
-        """The name of the original author of the line."""
+        """The name of the original author of the line, canonicalized through the mailmap."""
         ...
 
     @property
     def author_email(self) -> str:
-        """The email of the original author of the line."""
+        """The email of the original author of the line, canonicalized through the mailmap."""
         ...
 
     @property
@@ -141,9 +497,19 @@ class BlameLine:
         """Unix timestamp of when the line was authored."""
         ...
 
+    @property
+    def author_offset_minutes(self) -> int:
+        """The author's UTC offset, in minutes, when the line was authored."""
+        ...
+
+    @property
+    def author_datetime(self) -> datetime:
+        """Timezone-aware datetime when the line was authored, in the author's UTC offset."""
+        ...
+
     @property
     def committer(self) -> str:
-        """The name of the committer who last committed the line."""
+        """The name of the committer who last committed the line, canonicalized through the mailmap."""
         ...
 
     @property
@@ -151,18 +517,45 @@ class BlameLine:
         """Unix timestamp of when the line was committed."""
         ...
 
+    @property
+    def commit_offset_minutes(self) -> int:
+        """The committer's UTC offset, in minutes, when the line was committed."""
+        ...
+
+    @property
+    def commit_datetime(self) -> datetime:
+        """Timezone-aware datetime when the line was committed, in the committer's UTC offset."""
+        ...
+
     @property
     def summary(self) -> str:
         """The commit message summary associated with the line."""
         ...
 
+    @property
+    def raw_author(self) -> str:
+        """Author name exactly as recorded, before mailmap resolution."""
+        ...
 
-def get_file_blame(file_path: str) -> list[BlameLine]:
+    @property
+    def raw_author_email(self) -> str:
+        """Author email exactly as recorded, before mailmap resolution."""
+        ...
+
+    @property
+    def raw_committer(self) -> str:
+        """Committer name exactly as recorded, before mailmap resolution."""
+        ...
+
+
+def get_file_blame(file_path: str, mailmap: Optional[str] = None) -> list[BlameLine]:
     """
     Retrieve blame information for each line in a file tracked by Git.
 
     Parameters:
         file_path (str): The path to the file (absolute, relative, or just the filename in the current directory).
+        mailmap (Optional[str]): Mailmap text to use instead of the repository's
+            `.mailmap` and `mailmap.file` configuration.
 
     Returns:
         list[BlameLine]: A list of BlameLine objects with detailed commit attribution.
@@ -174,24 +567,108 @@ def get_file_blame(file_path: str) -> list[BlameLine]:
     ...
 
 
-def get_blame_for_files(file_paths: list[str]) -> dict[str, list[BlameLine]]:
+class BlameErrorKind:
+    """
+    Why a file could not be blamed.
+    """
+
+    NotFound: "BlameErrorKind"
+    """The path does not exist or could not be resolved."""
+    NotInRepository: "BlameErrorKind"
+    """No Git repository contains the path."""
+    OutsideWorkdir: "BlameErrorKind"
+    """The path is outside the repository's working directory (or the repository is bare)."""
+    Git: "BlameErrorKind"
+    """libgit2 failed while computing the blame."""
+    Io: "BlameErrorKind"
+    """The file could not be read from disk."""
+
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, ...]: ...
+
+
+class BlameError:
+    """
+    A file that could not be blamed by `get_blame_for_files`.
+    """
+
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, ...]: ...
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the fields as a plain dictionary, e.g. for `pandas.DataFrame`."""
+        ...
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "BlameError":
+        """Rebuild an instance from the output of `to_dict`."""
+        ...
+
+    @property
+    def path(self) -> str:
+        """The path exactly as it was passed in."""
+        ...
+
+    @property
+    def kind(self) -> BlameErrorKind:
+        """The category of failure."""
+        ...
+
+    @property
+    def message(self) -> str:
+        """Human-readable description of the failure."""
+        ...
+
+
+class BlameResult:
+    """
+    Outcome of a batch blame: the files that succeeded and the ones that failed.
+    Compares by value but is not hashable.
+    """
+
+    __hash__: ClassVar[None]  # type: ignore[assignment]
+
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __reduce__(self) -> tuple[Any, ...]: ...
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the fields as a plain dictionary, e.g. for `pandas.DataFrame`."""
+        ...
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "BlameResult":
+        """Rebuild an instance from the output of `to_dict`."""
+        ...
+
+    @property
+    def results(self) -> dict[str, list[BlameLine]]:
+        """Mapping from each successfully blamed path (as passed in) to its BlameLine objects."""
+        ...
+
+    @property
+    def errors(self) -> list[BlameError]:
+        """One entry per path that could not be blamed, in input order."""
+        ...
+
+
+def get_blame_for_files(file_paths: list[str], mailmap: Optional[str] = None) -> BlameResult:
     """
     Perform parallel blame analysis on multiple files.
 
-    Each file is analyzed concurrently using multiple threads for efficiency.
-    The result is returned as a dictionary where the key is the file path,
-    and the value is a list of BlameLine objects representing per-line attribution.
+    Files may belong to different repositories. They are grouped by the repository
+    that contains them and blamed concurrently, with each worker thread opening a
+    given repository only once. Failures do not abort the batch; they are reported
+    per path in `BlameResult.errors`.
 
     Parameters:
         file_paths (list[str]): A list of file paths (absolute or relative) to analyze.
+        mailmap (Optional[str]): Mailmap text to use for every repository instead of
+            each repository's own `.mailmap` and `mailmap.file` configuration.
 
     Returns:
-        dict[str, list[BlameLine]]: A mapping from file paths to lists of BlameLine objects,
-        each containing line-level commit attribution data.
-
-    Raises:
-        IOError: If a file cannot be accessed or read.
-        ValueError: If the file path cannot be resolved relative to the Git repository.
-        RuntimeError: If repository discovery or blame computation fails internally.
+        BlameResult: The per-file BlameLine lists and the per-file errors.
     """
-    ...
\ No newline at end of file
+    ...
@@ -0,0 +1,124 @@
+from typing import Any, Optional, Union
+
+
+class ConfigEntry:
+    """
+    A configuration variable and the level of the file that set it.
+    """
+
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, ...]: ...
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the fields as a plain dictionary, e.g. for `pandas.DataFrame`."""
+        ...
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "ConfigEntry":
+        """Rebuild an instance from the output of `to_dict`."""
+        ...
+
+    @property
+    def name(self) -> str:
+        """Variable name, e.g. `user.email`."""
+        ...
+
+    @property
+    def value(self) -> Optional[str]:
+        """Raw value; `None` for a bare boolean such as `[core] bare`."""
+        ...
+
+    @property
+    def level(self) -> str:
+        """`system`, `xdg`, `global`, `local`, `worktree`, `app` or `programdata`."""
+        ...
+
+
+class Config:
+    """
+    A git configuration: a repository's (`Repo.config`) with every level layered, or
+    the global levels (`Config.open`). Values are read from the highest level that
+    sets them and written to the highest level loaded.
+    """
+
+    @staticmethod
+    def open(level: str = "default") -> "Config":
+        """
+        Open the configuration outside any repository.
+
+        Parameters:
+            level (str): `default` layers the system, XDG and global files; `global`,
+                `xdg`, `system` or `programdata` open just that file.
+
+        Raises:
+            ValueError: If the level is unknown or its file does not exist.
+        """
+        ...
+
+    def get_string(self, name: str, default: Optional[str] = None) -> Optional[str]:
+        """Read a string, or `default` when the variable isn't set."""
+        ...
+
+    def get_bool(self, name: str, default: Optional[bool] = None) -> Optional[bool]:
+        """
+        Read a boolean, accepting git's spellings (`yes`, `on`, `1`, ...), or `default`
+        when the variable isn't set.
+
+        Raises:
+            ValueError: If the value is not a boolean.
+        """
+        ...
+
+    def get_int(self, name: str, default: Optional[int] = None) -> Optional[int]:
+        """
+        Read an integer, accepting the `k`, `m` and `g` suffixes, or `default` when the
+        variable isn't set.
+
+        Raises:
+            ValueError: If the value is not an integer.
+        """
+        ...
+
+    def get_path(self, name: str, default: Optional[str] = None) -> Optional[str]:
+        """Read a path, expanding a leading `~/`, or `default` when the variable isn't set."""
+        ...
+
+    def get_multivar(self, name: str, regexp: Optional[str] = None) -> list[str]:
+        """
+        Every value of a variable that may be set more than once, such as
+        `remote.origin.fetch`, optionally only those matching `regexp`.
+        """
+        ...
+
+    def set(self, name: str, value: Union[str, bool, int]) -> None:
+        """
+        Set a variable in the highest level loaded (`.git/config` for `Repo.config`).
+
+        Raises:
+            TypeError: If `value` is not a str, bool or int.
+        """
+        ...
+
+    def unset(self, name: str) -> None:
+        """
+        Remove a variable from the highest level loaded.
+
+        Raises:
+            KeyError: If the variable isn't set there.
+        """
+        ...
+
+    def entries(self, glob: Optional[str] = None) -> list[ConfigEntry]:
+        """
+        Every variable, or those whose whole name matches `glob`, where `*` matches
+        any run of characters and `?` any one, e.g. `user.*`, `*.email` or
+        `remote.*.url`. Section and variable names match case-insensitively,
+        as git treats them; subsection names are case-sensitive.
+        """
+        ...
+
+    def snapshot(self) -> "Config":
+        """A read-only copy that later changes to the files don't affect."""
+        ...
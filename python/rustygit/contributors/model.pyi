@@ -0,0 +1,66 @@
+from typing import Any
+
+
+class Contributor:
+    """
+    Aggregated activity of one contributor identity, after `.mailmap` resolution.
+    """
+
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, ...]: ...
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the fields as a plain dictionary, e.g. for `pandas.DataFrame`."""
+        ...
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "Contributor":
+        """Rebuild an instance from the output of `to_dict`."""
+        ...
+
+    @property
+    def name(self) -> str:
+        """Canonical name of the contributor."""
+        ...
+
+    @property
+    def email(self) -> str:
+        """Canonical email address of the contributor."""
+        ...
+
+    @property
+    def commits(self) -> int:
+        """Number of commits authored by the contributor."""
+        ...
+
+    @property
+    def insertions(self) -> int:
+        """Total lines added across the contributor's non-merge commits."""
+        ...
+
+    @property
+    def deletions(self) -> int:
+        """Total lines deleted across the contributor's non-merge commits."""
+        ...
+
+    @property
+    def first_commit_time(self) -> int:
+        """Timestamp (in seconds since epoch) of the contributor's earliest authored commit."""
+        ...
+
+    @property
+    def last_commit_time(self) -> int:
+        """Timestamp (in seconds since epoch) of the contributor's latest authored commit."""
+        ...
+
+    @property
+    def active_days(self) -> int:
+        """Number of distinct calendar days (in the author's timezone) with at least one commit."""
+        ...
+
+    @property
+    def files_touched(self) -> int:
+        """Number of distinct file paths changed by the contributor."""
+        ...
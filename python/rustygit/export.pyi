@@ -0,0 +1,134 @@
+from typing import Any, Optional
+
+from .commits.model import BlameError
+
+
+class ArrowTable:
+    """
+    Arrow record batches produced in Rust.
+
+    Implements the Arrow PyCapsule interface, so `pyarrow.table(t)`,
+    `polars.DataFrame(t)` and other Arrow consumers read the data without
+    copying it.
+
+    Timestamps are `timestamp[s, tz=UTC]`; the recorded UTC offsets are kept
+    in separate `*_offset_minutes` columns where available.
+    """
+
+    @property
+    def num_rows(self) -> int: ...
+    @property
+    def column_names(self) -> list[str]: ...
+    @property
+    def errors(self) -> list[BlameError]:
+        """Inputs that could not be exported. Only `export_blame` fills this in."""
+        ...
+    def __len__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __arrow_c_schema__(self) -> object: ...
+    def __arrow_c_stream__(self, requested_schema: Optional[object] = None) -> object: ...
+    def to_pyarrow(self) -> Any:
+        """Convert to a `pyarrow.Table`. Requires pyarrow >= 14."""
+        ...
+    def write_parquet(self, path: str) -> None:
+        """Write the table to a Snappy-compressed Parquet file."""
+        ...
+    def write_csv(self, path: str) -> None:
+        """Write the table to CSV with a header row. List columns are joined with spaces."""
+        ...
+    def write_jsonl(self, path: str) -> None:
+        """Write the table as JSON Lines, one object per row."""
+        ...
+
+
+def export_commits(
+    path: str,
+    rev: Optional[str] = None,
+    mailmap: Optional[str] = None,
+) -> ArrowTable:
+    """
+    Export commit history as an Arrow table, one row per commit.
+
+    Schema:
+        hash: string
+        tree_id: string
+        author: string
+        author_email: string
+        author_time: timestamp[s, tz=UTC]
+        author_offset_minutes: int32
+        committer: string
+        committer_email: string
+        commit_time: timestamp[s, tz=UTC]
+        commit_offset_minutes: int32
+        subject: string
+        message: string
+        parents: list<string>
+        is_signed: bool
+
+    Parameters:
+        path (str): Path to the Git repository.
+        rev (Optional[str]): A revision or `A..B` range. Defaults to HEAD.
+        mailmap (Optional[str]): Mailmap text to use instead of the repository's `.mailmap`.
+
+    Raises:
+        IOError: If the repository cannot be opened.
+        ValueError: If the revision cannot be resolved.
+    """
+    ...
+
+
+def export_file_stats(path: str, rev: Optional[str] = None) -> ArrowTable:
+    """
+    Export per-file line statistics, one row per file changed by each
+    non-merge commit, diffed against its first parent with rename detection.
+
+    Schema:
+        commit_hash: string
+        commit_time: timestamp[s, tz=UTC]
+        path: string
+        old_path: string (null unless renamed or copied)
+        status: string ("added", "deleted", "modified", "renamed", "copied" or "typechange")
+        additions: uint64
+        deletions: uint64
+        is_binary: bool
+
+    Parameters:
+        path (str): Path to the Git repository.
+        rev (Optional[str]): A revision or `A..B` range. Defaults to HEAD.
+
+    Raises:
+        IOError: If the repository cannot be opened.
+        ValueError: If the revision cannot be resolved.
+    """
+    ...
+
+
+def export_blame(file_paths: list[str], mailmap: Optional[str] = None) -> ArrowTable:
+    """
+    Blame many files in parallel and export the lines as one Arrow table,
+    in the order the files were given.
+
+    Schema:
+        path: string (as given)
+        line_number: uint32
+        content: string
+        commit_hash: string
+        author: string
+        author_email: string
+        author_time: timestamp[s, tz=UTC]
+        committer: string
+        commit_time: timestamp[s, tz=UTC]
+        summary: string
+
+    Parameters:
+        file_paths (list[str]): Files to blame; they may live in different repositories.
+        mailmap (Optional[str]): Mailmap text to use instead of each repository's `.mailmap`.
+
+    Returns:
+        ArrowTable: The lines of every file that could be blamed. Files that
+        failed are skipped and listed in the table's `errors`.
+
+    Raises:
+        ValueError: If the mailmap text cannot be parsed.
+    """
+    ...
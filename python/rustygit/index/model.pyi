@@ -0,0 +1,193 @@
+from typing import Any, Optional, Tuple
+
+
+class Conflict:
+    """
+    A path left conflicted in the index by a merge, cherry-pick, revert or stash
+    application. Sides missing from the conflict are None, e.g. the ancestor when
+    both sides added the file, or "theirs" when they deleted it.
+    """
+
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, ...]: ...
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the fields as a plain dictionary, e.g. for `pandas.DataFrame`."""
+        ...
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "Conflict":
+        """Rebuild an instance from the output of `to_dict`."""
+        ...
+
+    @property
+    def path(self) -> str:
+        """The conflicted path: ours if present, else theirs, else the ancestor's."""
+        ...
+
+    @property
+    def ancestor_path(self) -> Optional[str]: ...
+    @property
+    def ancestor_id(self) -> Optional[str]:
+        """Blob id of the common ancestor's version (index stage 1)."""
+        ...
+
+    @property
+    def our_path(self) -> Optional[str]: ...
+    @property
+    def our_id(self) -> Optional[str]:
+        """Blob id of our version (index stage 2)."""
+        ...
+
+    @property
+    def their_path(self) -> Optional[str]: ...
+    @property
+    def their_id(self) -> Optional[str]:
+        """Blob id of their version (index stage 3)."""
+        ...
+
+
+class IndexEntry:
+    """
+    A stage entry of an index: stage 0 for a merged path, 1-3 for the ancestor, our
+    and their sides of a conflict.
+    """
+
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, ...]: ...
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the fields as a plain dictionary, e.g. for `pandas.DataFrame`."""
+        ...
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "IndexEntry":
+        """Rebuild an instance from the output of `to_dict`."""
+        ...
+
+    @property
+    def path(self) -> str:
+        """Path relative to the repository root."""
+        ...
+
+    @property
+    def mode(self) -> int:
+        """File mode, e.g. `0o100644`."""
+        ...
+
+    @property
+    def id(self) -> str:
+        """Blob id."""
+        ...
+
+    @property
+    def stage(self) -> int:
+        """0 when merged, 1-3 for the ancestor, our and their side of a conflict."""
+        ...
+
+    @property
+    def size(self) -> int:
+        """Size recorded for the working tree file; 0 for entries that never had one, such as merge results."""
+        ...
+
+
+class Index:
+    """
+    A git index: the repository's own (`Repo.index`) or one held in memory, such as
+    the result of `Repo.merge_commits`.
+    """
+
+    def __len__(self) -> int: ...
+    def __repr__(self) -> str: ...
+
+    @property
+    def has_conflicts(self) -> bool:
+        """Whether any path is conflicted."""
+        ...
+
+    @property
+    def parents(self) -> list[str]:
+        """Commits `write_commit` uses as parents when none are given."""
+        ...
+
+    def conflicts(self) -> list[Conflict]:
+        """The conflicted paths, in path order."""
+        ...
+
+    def conflict_content(
+        self, path: str
+    ) -> Tuple[Optional[bytes], Optional[bytes], Optional[bytes]]:
+        """
+        Read the sides of the conflict on `path`.
+
+        Returns:
+            Tuple[Optional[bytes], Optional[bytes], Optional[bytes]]: The ancestor, our and
+            their content; a side is `None` where it is missing from the conflict.
+
+        Raises:
+            ValueError: If `path` is not conflicted.
+        """
+        ...
+
+    def resolve(self, path: str, content: Optional[bytes]) -> None:
+        """
+        Mark the conflict on `path` resolved with `content`, or with the path deleted when
+        `content` is `None`. The working tree is left alone.
+
+        Raises:
+            ValueError: If `path` is not conflicted.
+        """
+        ...
+
+    def take_ours(self, path: str) -> None:
+        """Resolve the conflict on `path` with our side (deleting the path if we deleted it)."""
+        ...
+
+    def take_theirs(self, path: str) -> None:
+        """Resolve the conflict on `path` with their side (deleting the path if they deleted it)."""
+        ...
+
+    def entries(self) -> list[IndexEntry]:
+        """Every entry, including the stages of conflicted paths, in path order."""
+        ...
+
+    def write(self) -> None:
+        """
+        Save the index back to the repository's index file.
+
+        Raises:
+            ValueError: For indexes held only in memory, which have no file.
+        """
+        ...
+
+    def write_tree(self) -> str:
+        """
+        Write the index as a tree object.
+
+        Returns:
+            str: The id of the tree.
+
+        Raises:
+            ValueError: If conflicts remain.
+        """
+        ...
+
+    def write_commit(self, message: str, parents: Optional[list[str]] = None) -> str:
+        """
+        Write the index as a commit object without updating any ref.
+
+        Parameters:
+            message (str): The commit message.
+            parents (Optional[list[str]]): Parent revisions. Defaults to the commits that were merged.
+
+        Returns:
+            str: The id of the commit.
+
+        Raises:
+            ValueError: If conflicts remain.
+        """
+        ...
@@ -0,0 +1,186 @@
+from typing import Any, Dict, Optional, Tuple
+
+from ..index.model import Conflict
+
+
+class RevertResult:
+    """
+    Outcome of `Repo.revert`: the new commit, or the conflicts that stopped it.
+    """
+
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, ...]: ...
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the fields as a plain dictionary, e.g. for `pandas.DataFrame`."""
+        ...
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "RevertResult":
+        """Rebuild an instance from the output of `to_dict`."""
+        ...
+
+    @property
+    def commit_id(self) -> Optional[str]:
+        """Id of the revert commit; None when it was not committed or conflicted."""
+        ...
+
+    @property
+    def conflicts(self) -> list[Conflict]:
+        """Paths left conflicted in the index; empty when the revert applied cleanly."""
+        ...
+
+    @property
+    def has_conflicts(self) -> bool:
+        """Whether the revert stopped with conflicts."""
+        ...
+
+
+class CherryPickResult:
+    """
+    Progress of `Repo.cherry_pick`. When `current` is set the sequence stopped on
+    conflicts; resolve them and pass the result to `Repo.cherry_pick_continue`, or
+    give up with `Repo.cherry_pick_abort`.
+    """
+
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, ...]: ...
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the fields as a plain dictionary, e.g. for `pandas.DataFrame`."""
+        ...
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "CherryPickResult":
+        """Rebuild an instance from the output of `to_dict`."""
+        ...
+
+    @property
+    def original_head(self) -> str:
+        """Id of HEAD before the cherry-pick, i.e. before switching to `onto`."""
+        ...
+
+    @property
+    def head_name(self) -> Optional[str]:
+        """Branch HEAD was on before the cherry-pick, or `None` if it was detached."""
+        ...
+
+    @property
+    def base(self) -> str:
+        """Id of the commit the first pick was applied on top of."""
+        ...
+
+    @property
+    def applied(self) -> list[str]:
+        """Ids of the new commits, in order."""
+        ...
+
+    @property
+    def skipped(self) -> list[str]:
+        """Commits left out because their changes were already present."""
+        ...
+
+    @property
+    def conflicts(self) -> list[Conflict]:
+        """Paths left conflicted by `current`."""
+        ...
+
+    @property
+    def current(self) -> Optional[str]:
+        """The commit whose pick stopped with conflicts, if any."""
+        ...
+
+    @property
+    def remaining(self) -> list[str]:
+        """Commits still to pick after `current`."""
+        ...
+
+    @property
+    def mainline(self) -> Optional[int]:
+        """Parent number used when picking merge commits; ignored for other commits."""
+        ...
+
+    @property
+    def has_conflicts(self) -> bool:
+        """Whether the sequence stopped with conflicts."""
+        ...
+
+
+PlanStep = Tuple[str, str, Optional[str]]
+
+
+class RebaseResult:
+    """
+    Progress of `Repo.rebase`. The rebase happens in memory: refs and the working
+    tree are only updated once every step has applied. When `current` is set the
+    rebase stopped on conflicts; pass the result to `Repo.rebase_continue` with the
+    resolved contents, `Repo.rebase_skip` or `Repo.rebase_abort`.
+    """
+
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, ...]: ...
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the fields as a plain dictionary, e.g. for `pandas.DataFrame`."""
+        ...
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "RebaseResult":
+        """Rebuild an instance from the output of `to_dict`."""
+        ...
+
+    @property
+    def original_head(self) -> str:
+        """Id of HEAD before the rebase started."""
+        ...
+
+    @property
+    def head_name(self) -> Optional[str]:
+        """Branch being rebased, e.g. `refs/heads/feature`; `None` when HEAD was detached."""
+        ...
+
+    @property
+    def onto(self) -> str:
+        """Commit the rewritten commits are replayed onto."""
+        ...
+
+    @property
+    def new_head(self) -> str:
+        """Tip of the rewritten commits so far."""
+        ...
+
+    @property
+    def mapping(self) -> Dict[str, str]:
+        """Old commit id to rewritten commit id; squashed commits map to the commit they were folded into."""
+        ...
+
+    @property
+    def skipped(self) -> list[str]:
+        """Commits left out because their changes were already present."""
+        ...
+
+    @property
+    def conflicts(self) -> list[Conflict]:
+        """Paths left conflicted by `current`."""
+        ...
+
+    @property
+    def current(self) -> Optional[PlanStep]:
+        """The `(action, commit_id, message)` step that stopped with conflicts, if any."""
+        ...
+
+    @property
+    def remaining(self) -> list[PlanStep]:
+        """Steps still to apply after `current`."""
+        ...
+
+    @property
+    def has_conflicts(self) -> bool:
+        """Whether the rebase stopped with conflicts."""
+        ...
@@ -0,0 +1,75 @@
+from typing import Any, ClassVar, Optional
+
+from .commits.model import BlameError
+
+
+class OwnershipResult:
+    """
+    Outcome of `code_ownership`: the ownership rows and the files that could not be blamed.
+    Compares by value but is not hashable.
+    """
+
+    __hash__: ClassVar[None]  # type: ignore[assignment]
+
+    def __len__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __reduce__(self) -> tuple[Any, ...]: ...
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the fields as a plain dictionary."""
+        ...
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "OwnershipResult":
+        """Rebuild an instance from the output of `to_dict`."""
+        ...
+
+    @property
+    def rows(self) -> OwnershipResult:
+        """
+        Tidy rows, one per author per scope, with the keys `scope` ("total",
+        "directory" or "file"), `path` (empty for the total), `author`, `author_email`,
+        `lines`, `total_lines`, `percentage` and `last_touched` (Unix timestamp).
+        Pass them to `pandas.DataFrame`.
+        """
+        ...
+
+    @property
+    def errors(self) -> list[BlameError]:
+        """One entry per file that could not be blamed and is left out of `rows`."""
+        ...
+
+
+def code_ownership(
+    repo: str,
+    rev: str = "HEAD",
+    pathspec: Optional[list[str]] = None,
+    exclude: Optional[list[str]] = None,
+    mailmap: Optional[str] = None,
+) -> OwnershipResult:
+    """
+    Compute who owns which code by blaming every matching file at a revision.
+
+    Files are blamed in parallel and surviving lines are attributed to the
+    author of the commit that last touched them, with `.mailmap` applied.
+    Binary files are skipped.
+
+    Parameters:
+        repo (str): Path to the Git repository.
+        rev (str): Revision to blame at. Defaults to "HEAD".
+        pathspec (Optional[list[str]]): Git pathspecs selecting files to include. Defaults to all files.
+        exclude (Optional[list[str]]): Git pathspecs selecting files to leave out.
+        mailmap (Optional[str]): Mailmap text to use instead of the repository's
+            `.mailmap` and `mailmap.file` configuration.
+
+    Returns:
+        OwnershipResult: The ownership rows, and the files that could not be blamed.
+        A file that fails to blame is left out and reported in `errors` instead of
+        failing the whole report.
+
+    Raises:
+        IOError: If the repository cannot be opened.
+        ValueError: If the revision cannot be resolved.
+    """
+    ...
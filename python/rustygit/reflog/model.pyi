@@ -0,0 +1,64 @@
+from datetime import datetime
+from typing import Any, Optional
+
+from ..commits.model import Identity
+
+
+class ReflogEntry:
+    """
+    An entry of a ref's reflog. Index 0 is the most recent entry (`HEAD@{0}`).
+    """
+
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, ...]: ...
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the fields as a plain dictionary, e.g. for `pandas.DataFrame`."""
+        ...
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "ReflogEntry":
+        """Rebuild an instance from the output of `to_dict`."""
+        ...
+
+    @property
+    def index(self) -> int:
+        """Position in the reflog, as in `HEAD@{index}`."""
+        ...
+
+    @property
+    def old_id(self) -> str:
+        """Where the ref pointed before the update; all zeros when it was created."""
+        ...
+
+    @property
+    def new_id(self) -> str:
+        """Where the ref pointed after the update."""
+        ...
+
+    @property
+    def committer(self) -> Identity:
+        """Who made the update."""
+        ...
+
+    @property
+    def time(self) -> int:
+        """Time of the update (Unix timestamp)."""
+        ...
+
+    @property
+    def offset_minutes(self) -> int:
+        """UTC offset of `time` in minutes."""
+        ...
+
+    @property
+    def message(self) -> Optional[str]:
+        """The reflog message, e.g. "commit: Fix parser"."""
+        ...
+
+    @property
+    def datetime(self) -> datetime:
+        """Time of the update as a timezone-aware datetime."""
+        ...
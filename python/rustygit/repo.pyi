@@ -1,3 +1,19 @@
+from typing import Dict, List, Optional, Sequence, Tuple, Union
+
+from .commits.model import Commit, DiffEntry
+from .config.model import Config
+from .contributors.model import Contributor
+from .index.model import Conflict, Index
+from .operations.model import CherryPickResult, RebaseResult, RevertResult
+from .reflog.model import ReflogEntry
+from .revision.model import GitObject, RevRange
+from .search.model import GrepMatch, PickaxeMatch
+from .stash.model import Stash
+from .submodule.model import Submodule
+from .tree.model import BlobIter, TreeEntry
+from .worktree.model import Worktree
+
+
 class Repo:
     """
     A Python-friendly Git repository handler powered by Rust for performance and parallelism.
@@ -107,6 +123,741 @@ def clone(url: str, path: Optional[str] = None, username: Optional[str] = None,
         """
         ...
 
+    def contributors(
+        self,
+        range: Optional[str] = None,
+        paths: Optional[List[str]] = None,
+        since: Optional[int] = None,
+        until: Optional[int] = None,
+        mailmap: Optional[str] = None,
+    ) -> List[Contributor]:
+        """
+        Compute per-contributor statistics, similar to `git shortlog` with line counts.
+
+        Commits are processed in parallel and identities are merged using the
+        repository's `.mailmap`. Merge commits are counted but carry no line stats.
+
+        Parameters:
+            range (Optional[str]): A revision or `A..B` range to walk. Defaults to HEAD.
+            paths (Optional[List[str]]): Pathspecs limiting which commits and changes are counted.
+            since (Optional[int]): Only count commits authored at or after this Unix timestamp.
+            until (Optional[int]): Only count commits authored at or before this Unix timestamp.
+            mailmap (Optional[str]): Mailmap text to use instead of the repository's
+                `.mailmap` and `mailmap.file` configuration.
+
+        Returns:
+            List[Contributor]: One entry per identity, sorted by commit count (highest first).
+
+        Raises:
+            ValueError: If the range cannot be resolved.
+        """
+        ...
+
+    def merge_base(self, a: str, b: str) -> Optional[str]:
+        """
+        Find the best common ancestor of two revisions.
+
+        Parameters:
+            a (str): Any revspec resolving to a commit.
+            b (str): Any revspec resolving to a commit.
+
+        Returns:
+            Optional[str]: Commit id of the merge base, or None if the histories are unrelated.
+        """
+        ...
+
+    def merge_bases(self, *revs: str, octopus: bool = False) -> List[str]:
+        """
+        Find all merge bases of two or more revisions, like `git merge-base --all`.
+
+        Parameters:
+            *revs (str): At least two revspecs resolving to commits.
+            octopus (bool): Compute the base of an octopus merge of all revisions instead,
+                like `git merge-base --octopus`.
+
+        Returns:
+            List[str]: Commit ids of the merge bases; empty if the histories are unrelated.
+
+        Raises:
+            ValueError: If fewer than two revisions are given or one cannot be resolved.
+        """
+        ...
+
+    def is_ancestor(self, ancestor: str, descendant: str) -> bool:
+        """
+        Check whether `ancestor` is reachable from `descendant`.
+        A commit is considered its own ancestor.
+        """
+        ...
+
+    def ahead_behind(self, local: str, upstream: str) -> Tuple[int, int]:
+        """
+        Count commits unique to each side, as shown by `git status` for a tracking branch.
+
+        Returns:
+            Tuple[int, int]: (ahead, behind) — commits in `local` not in `upstream`,
+            and commits in `upstream` not in `local`.
+        """
+        ...
+
+    def rev_parse(self, spec: str) -> GitObject:
+        """
+        Resolve a revspec such as `HEAD~3`, `v1.2^{tree}`, `HEAD:path/to/file` or `main@{upstream}`.
+
+        Returns:
+            GitObject: The object the revspec names, without peeling tags.
+
+        Raises:
+            ValueError: If the revspec cannot be resolved.
+        """
+        ...
+
+    def rev_parse_range(self, spec: str) -> RevRange:
+        """
+        Resolve an `A..B` or `A...B` range into its endpoints.
+
+        Raises:
+            ValueError: If the spec is not a range or cannot be resolved.
+        """
+        ...
+
+    def short_id(self, rev: str) -> str:
+        """
+        Return the shortest unambiguous abbreviation of the object `rev` resolves to.
+        """
+        ...
+
+    def commit(self, rev: str = "HEAD", mailmap: Optional[str] = None) -> Commit:
+        """
+        Look up a single commit.
+
+        Parameters:
+            rev (str): Any revspec resolving to a commit; tags are peeled. Defaults to HEAD.
+            mailmap (Optional[str]): Mailmap text to use instead of the repository's `.mailmap`.
+
+        Raises:
+            ValueError: If the revision cannot be resolved to a commit.
+        """
+        ...
+
+    def ls_tree(self, rev: str = "HEAD", path: Optional[str] = None, recursive: bool = False) -> List[TreeEntry]:
+        """
+        List a tree at a revision, like `git ls-tree`.
+
+        Parameters:
+            rev (str): Any revspec resolving to a commit or tree. Defaults to HEAD.
+            path (Optional[str]): Directory to list instead of the root. If it names a file,
+                a single entry describing that file is returned.
+            recursive (bool): Descend into subdirectories and list only files, like `ls-tree -r`.
+
+        Raises:
+            FileNotFoundError: If `path` does not exist at `rev`.
+            ValueError: If the revision cannot be resolved.
+        """
+        ...
+
+    def read_file(self, rev: str, path: str) -> bytes:
+        """
+        Read a file's content as of a revision, without checking it out.
+
+        Raises:
+            FileNotFoundError: If no file exists at `path` in `rev`.
+            ValueError: If the revision cannot be resolved.
+        """
+        ...
+
+    def file_exists(self, rev: str, path: str) -> bool:
+        """
+        Check whether a file (or symlink) exists at `path` as of a revision.
+        Directories and submodules do not count as files.
+        """
+        ...
+
+    def iter_blobs(
+        self,
+        rev: str = "HEAD",
+        pathspec: Optional[List[str]] = None,
+        max_size: Optional[int] = None,
+        skip_binary: bool = False,
+    ) -> BlobIter:
+        """
+        Stream the files at a revision, reading each blob only when the iterator reaches it.
+
+        Parameters:
+            rev (str): Any revspec resolving to a commit or tree. Defaults to HEAD.
+            pathspec (Optional[List[str]]): Git pathspecs selecting files. Defaults to all files.
+            max_size (Optional[int]): Leave out files larger than this many bytes.
+            skip_binary (bool): Leave out files Git considers binary.
+
+        Raises:
+            ValueError: If the revision or a pathspec is invalid.
+        """
+        ...
+
+    def file_history(
+        self,
+        path: str,
+        rev: Optional[str] = None,
+        mailmap: Optional[str] = None,
+    ) -> List[Tuple[Commit, Optional[str], Optional[str], DiffEntry]]:
+        """
+        List the commits that changed a file, following renames like `git log --follow`.
+
+        Each commit is compared with its first parent; merges that kept one parent's
+        version of the file are skipped.
+
+        Parameters:
+            path (str): Path of the file at `rev`, relative to the repository root.
+                The file may have been deleted, in which case history starts at the deletion.
+            rev (Optional[str]): A revision or `A..B` range to walk. Defaults to HEAD.
+            mailmap (Optional[str]): Mailmap text to use instead of the repository's `.mailmap`.
+
+        Returns:
+            List[Tuple[Commit, Optional[str], Optional[str], DiffEntry]]: Newest first,
+            `(commit, old_path, new_path, stats)` where `old_path` is None when the file was
+            added and `new_path` is None when it was deleted.
+
+        Raises:
+            ValueError: If the revision cannot be resolved.
+        """
+        ...
+
+    def pickaxe(
+        self,
+        pattern: str,
+        regex: bool = False,
+        paths: Optional[List[str]] = None,
+        range: Optional[str] = None,
+        mode: str = "S",
+        ignore_case: bool = False,
+        mailmap: Optional[str] = None,
+    ) -> List[PickaxeMatch]:
+        """
+        Find the commits that introduced or removed a string, like `git log -S` / `git log -G`.
+
+        Non-merge commits are diffed against their parent in parallel. Binary files are
+        ignored in "S" mode.
+
+        Parameters:
+            pattern (str): The string (or regular expression, see `regex`) to look for.
+            regex (bool): Treat `pattern` as a regular expression instead of a literal string.
+            paths (Optional[List[str]]): Pathspecs limiting which files are searched.
+            range (Optional[str]): A revision or `A..B` range to walk. Defaults to HEAD.
+            mode (str): "S" reports commits that change the number of occurrences of the
+                pattern in a file; "G" reports commits with an added or removed line matching it.
+            ignore_case (bool): Match case-insensitively.
+            mailmap (Optional[str]): Mailmap text to use instead of the repository's `.mailmap`.
+
+        Returns:
+            List[PickaxeMatch]: Matching commits, newest first, with the matching hunks.
+
+        Raises:
+            ValueError: If the pattern, mode or range is invalid.
+        """
+        ...
+
+    def grep(
+        self,
+        pattern: str,
+        rev: Optional[str] = None,
+        paths: Optional[List[str]] = None,
+        ignore_case: bool = False,
+        regex: bool = False,
+        max_count: Optional[int] = None,
+        context_lines: int = 0,
+        cached: bool = False,
+    ) -> List[GrepMatch]:
+        """
+        Search file contents like `git grep`, without checking anything out.
+
+        Files are searched in parallel and binary files are skipped.
+
+        Parameters:
+            pattern (str): The string (or regular expression, see `regex`) to look for.
+            rev (Optional[str]): Search the tree of this revision. When omitted, the tracked
+                files of the working tree are searched.
+            paths (Optional[List[str]]): Pathspecs limiting which files are searched.
+            ignore_case (bool): Match case-insensitively.
+            regex (bool): Treat `pattern` as a regular expression instead of a literal string.
+            max_count (Optional[int]): Report at most this many matches in each file, like
+                `git grep -m`; it does not limit the total across files.
+            context_lines (int): Number of lines of context to include before and after each match.
+            cached (bool): Search the staged content in the index instead of the working tree.
+                Cannot be combined with `rev`.
+
+        Returns:
+            List[GrepMatch]: Matches ordered by path and line number.
+
+        Raises:
+            ValueError: If the pattern or revision is invalid, both `rev` and `cached` are
+                given, or the working tree of a bare repository is searched.
+        """
+        ...
+
+    def stash_save(
+        self,
+        message: Optional[str] = None,
+        include_untracked: bool = False,
+        keep_index: bool = False,
+    ) -> Optional[str]:
+        """
+        Stash local changes, like `git stash push`.
+
+        Parameters:
+            message (Optional[str]): Description stored with the stash.
+            include_untracked (bool): Also stash (and remove) untracked files.
+            keep_index (bool): Leave staged changes in the index and working tree.
+
+        Returns:
+            Optional[str]: Id of the stash commit, or None if there was nothing to stash.
+
+        Raises:
+            ValueError: If no committer identity is configured.
+        """
+        ...
+
+    def stash_list(self) -> List[Stash]:
+        """
+        List stash entries, most recent first.
+        """
+        ...
+
+    def stash_apply(self, index: int = 0, reinstate_index: bool = False) -> List[Conflict]:
+        """
+        Apply a stash entry, keeping it in the stash list.
+
+        Parameters:
+            index (int): Which entry to apply (`stash@{index}`). Defaults to the latest.
+            reinstate_index (bool): Also restore which changes were staged, like `--index`.
+
+        Returns:
+            List[Conflict]: Paths left conflicted in the index; empty if the stash applied cleanly.
+
+        Raises:
+            IndexError: If there is no such stash entry.
+            ValueError: If applying would overwrite uncommitted local changes.
+        """
+        ...
+
+    def stash_pop(self, index: int = 0, reinstate_index: bool = False) -> List[Conflict]:
+        """
+        Apply a stash entry and drop it. As with `git stash pop`, the entry is kept
+        if applying it left conflicts.
+
+        Returns:
+            List[Conflict]: Paths left conflicted in the index; empty if the stash applied cleanly.
+
+        Raises:
+            IndexError: If there is no such stash entry.
+            ValueError: If applying would overwrite uncommitted local changes.
+        """
+        ...
+
+    def stash_drop(self, index: int = 0) -> None:
+        """
+        Remove a stash entry.
+
+        Raises:
+            IndexError: If there is no such stash entry.
+        """
+        ...
+
+    def stash_show(self, index: int = 0) -> List[DiffEntry]:
+        """
+        Summarize the changes in a stash entry against the commit it was based on,
+        like `git stash show --numstat`. Stashed untracked files are not included.
+
+        Raises:
+            IndexError: If there is no such stash entry.
+        """
+        ...
+
+    def reset(self, rev: str = "HEAD", mode: str = "mixed") -> None:
+        """
+        Move HEAD (and the current branch) to a revision, like `git reset`.
+
+        Parameters:
+            rev (str): Any revspec resolving to a commit. Defaults to HEAD.
+            mode (str): "soft" keeps the index and working tree, "mixed" resets the index,
+                "hard" resets the index and working tree, discarding local changes.
+
+        Raises:
+            ValueError: If the mode is unknown or the revision cannot be resolved.
+        """
+        ...
+
+    def revert(self, commit: str, mainline: Optional[int] = None, commit_result: bool = True) -> RevertResult:
+        """
+        Revert a commit on top of HEAD, like `git revert`.
+
+        Parameters:
+            commit (str): Any revspec resolving to the commit to revert.
+            mainline (Optional[int]): For a merge commit, the 1-based parent number to revert to.
+            commit_result (bool): Commit the revert when it applies cleanly. When False the
+                changes are left staged, like `git revert --no-commit`.
+
+        Returns:
+            RevertResult: The new commit id, or the conflicts left in the index and working tree.
+            On conflicts the repository stays in the reverting state, so `git revert --continue`
+            or `--abort` can finish the operation.
+
+        Raises:
+            ValueError: If the commit cannot be resolved, a merge is reverted without `mainline`,
+                or local changes would be overwritten.
+        """
+        ...
+
+    def index(self) -> Index:
+        """
+        The repository's index, reloaded from disk, e.g. to resolve conflicts left by a
+        merge, cherry-pick or stash. Save changes with `Index.write`.
+        """
+        ...
+
+    def merge_trees(self, ancestor: Optional[str], ours: str, theirs: str) -> Index:
+        """
+        Three-way merge of two trees in memory, leaving the working tree, index and refs alone.
+
+        Parameters:
+            ancestor (Optional[str]): Revision of the common ancestor tree; `None` for the empty tree.
+            ours (str): Revision of our tree.
+            theirs (str): Revision of their tree.
+
+        Returns:
+            Index: The merged index, possibly with conflicts.
+        """
+        ...
+
+    def merge_commits(self, ours: str, theirs: str) -> Index:
+        """
+        Merge two commits from their merge base in memory, like `git merge-tree --write-tree`.
+        The working tree, index and refs are left alone.
+
+        Parameters:
+            ours (str): Revision of our commit.
+            theirs (str): Revision of their commit.
+
+        Returns:
+            Index: The merged index, with `parents` set to both commits.
+        """
+        ...
+
+    def cherry_pick(
+        self,
+        commits: Union[str, List[str]],
+        onto: Optional[str] = None,
+        mainline: Optional[int] = None,
+    ) -> CherryPickResult:
+        """
+        Apply one or more commits in order on top of HEAD, like `git cherry-pick`.
+
+        Each new commit keeps the original author and message. Commits whose changes
+        are already present are skipped. The sequence stops at the first conflict,
+        leaving it in the index and working tree.
+
+        Parameters:
+            commits (Union[str, List[str]]): Revspecs of the commits to pick, oldest first.
+            onto (Optional[str]): Branch (or revision, detaching HEAD) to check out first.
+            mainline (Optional[int]): For merge commits, the 1-based parent to diff against.
+                Non-merge commits are picked as usual.
+
+        Returns:
+            CherryPickResult: The new commit ids and, if stopped, the conflicts and
+            the commits still to pick.
+
+        Raises:
+            ValueError: If a commit cannot be resolved or local changes would be overwritten.
+                HEAD is back where it started if the picks fail after checking out `onto`.
+        """
+        ...
+
+    def cherry_pick_continue(self, result: CherryPickResult) -> CherryPickResult:
+        """
+        Commit the resolved pick that stopped `result` and pick the remaining commits.
+
+        Raises:
+            ValueError: If `result` did not stop on a conflict or conflicts remain in the index.
+        """
+        ...
+
+    def cherry_pick_abort(self, result: CherryPickResult) -> None:
+        """
+        Abandon a stopped cherry-pick. The branch the commits were picked onto is
+        reset to `result.base`, and HEAD returns to the branch (or detached commit)
+        it was on before the cherry-pick, with the index and working tree to match.
+
+        Raises:
+            ValueError: If `result` did not stop on a conflict.
+        """
+        ...
+
+    def rebase(
+        self,
+        upstream: str,
+        onto: Optional[str] = None,
+        interactive_plan: Optional[Sequence[Union[Tuple[str, str], Tuple[str, str, Optional[str]]]]] = None,
+    ) -> RebaseResult:
+        """
+        Rebase the commits between `upstream` and HEAD onto `onto`, like `git rebase [-i]`.
+
+        The commits are replayed in memory, so nothing changes until every step has
+        applied; the branch is then moved and checked out. Merge commits are left out.
+        Like git, it refuses to start while tracked files have uncommitted changes.
+
+        Parameters:
+            upstream (str): Revision whose history the rebased commits leave out.
+            onto (Optional[str]): Revision to replay the commits onto. Defaults to `upstream`.
+            interactive_plan (Optional[Sequence[tuple]]): `(action, commit)` or
+                `(action, commit, message)` tuples, applied in order. Actions are `pick`,
+                `drop`, `reword` (needs a message), `squash` (optional message; defaults to
+                both messages joined) and `fixup`. Commits left out of the plan are dropped.
+                By default every commit is picked.
+
+        Returns:
+            RebaseResult: The old -> new commit mapping and, if stopped, the conflicts
+            and the steps still to apply.
+
+        Raises:
+            ValueError: If the plan is invalid, tracked files have uncommitted changes, or
+                the checkout would overwrite local changes.
+        """
+        ...
+
+    def rebase_continue(
+        self,
+        result: RebaseResult,
+        resolved: Optional[Dict[str, Optional[bytes]]] = None,
+    ) -> RebaseResult:
+        """
+        Apply the step that stopped `result` with the conflicted paths resolved, then the
+        remaining steps.
+
+        Parameters:
+            result (RebaseResult): A rebase stopped on conflicts.
+            resolved (Optional[Dict[str, Optional[bytes]]]): Resolved content per conflicted
+                path, or `None` to delete the path.
+
+        Raises:
+            ValueError: If `result` did not stop on a conflict or conflicts remain unresolved.
+        """
+        ...
+
+    def rebase_skip(self, result: RebaseResult) -> RebaseResult:
+        """Leave out the step that stopped `result` and apply the remaining steps."""
+        ...
+
+    def rebase_abort(self, result: RebaseResult) -> None:
+        """
+        Abandon a stopped rebase, like `git rebase --abort`.
+
+        Nothing is written until a rebase finishes, so the branch, index and working
+        tree are left exactly as they are, including any work done since it stopped.
+
+        Raises:
+            ValueError: If `result` did not stop on a conflict.
+        """
+        ...
+
+    def submodules(self) -> List[Submodule]:
+        """
+        List the repository's submodules with their recorded and checked-out commits.
+        """
+        ...
+
+    def submodule_init(self, paths: Optional[List[str]] = None, overwrite: bool = False) -> None:
+        """
+        Copy submodule URLs from `.gitmodules` into `.git/config`, like `git submodule init`.
+
+        Parameters:
+            paths (Optional[List[str]]): Names or paths of the submodules. Defaults to all.
+            overwrite (bool): Replace URLs that are already configured.
+
+        Raises:
+            ValueError: If a submodule does not exist.
+        """
+        ...
+
+    def submodule_update(
+        self,
+        paths: Optional[List[str]] = None,
+        init: bool = False,
+        recursive: bool = False,
+    ) -> None:
+        """
+        Clone missing submodules and check out the commits recorded in the index, like
+        `git submodule update [--init] [--recursive]`.
+
+        Parameters:
+            paths (Optional[List[str]]): Names or paths of the submodules. Defaults to all.
+            init (bool): Initialize submodules that are not initialized yet.
+            recursive (bool): Also update the submodules of each submodule.
+
+        Raises:
+            ValueError: If a submodule does not exist or cannot be fetched.
+        """
+        ...
+
+    def submodule_sync(self, paths: Optional[List[str]] = None) -> None:
+        """
+        Copy submodule URLs from `.gitmodules` into the configs of the superproject and of
+        each checked-out submodule, like `git submodule sync`.
+
+        Parameters:
+            paths (Optional[List[str]]): Names or paths of the submodules. Defaults to all.
+        """
+        ...
+
+    def open_submodule(self, name: str) -> "Repo":
+        """
+        Open a checked-out submodule as a repository.
+
+        Parameters:
+            name (str): Name or path of the submodule.
+
+        Raises:
+            ValueError: If the submodule does not exist or is not checked out.
+        """
+        ...
+
+    def worktrees(self) -> List[Worktree]:
+        """List the repository's linked worktrees. The main worktree is not listed."""
+        ...
+
+    def add_worktree(
+        self,
+        name: str,
+        path: str,
+        ref: Optional[str] = None,
+        lock: bool = False,
+    ) -> Worktree:
+        """
+        Create a linked worktree, like `git worktree add`.
+
+        Parameters:
+            name (str): Name of the worktree.
+            path (str): Directory to check it out in; must not exist yet.
+            ref (Optional[str]): Local branch to check out. When `None`, or a revision that
+                isn't a local branch, a new branch called `name` is created at HEAD or at
+                that revision.
+            lock (bool): Lock the new worktree against pruning.
+
+        Returns:
+            Worktree: The new worktree.
+        """
+        ...
+
+    def prune_worktree(self, name: str, working_tree: bool = True, force: bool = False) -> None:
+        """
+        Remove a worktree's administrative files, like `git worktree prune`.
+
+        Parameters:
+            name (str): Name of the worktree.
+            working_tree (bool): Also delete the worktree's directory.
+            force (bool): Prune even if the worktree is valid or locked.
+
+        Raises:
+            ValueError: If the worktree does not exist, or is valid or locked without `force`.
+        """
+        ...
+
+    def lock_worktree(self, name: str, reason: Optional[str] = None) -> None:
+        """Lock a worktree so it isn't pruned, optionally recording why."""
+        ...
+
+    def unlock_worktree(self, name: str) -> None:
+        """Unlock a worktree locked by `lock_worktree` or `git worktree lock`."""
+        ...
+
+    def open_worktree(self, name: str) -> "Repo":
+        """
+        Open a linked worktree as a repository.
+
+        Raises:
+            ValueError: If the worktree does not exist.
+        """
+        ...
+
+    def is_worktree(self) -> bool:
+        """Whether this repository is a linked worktree of another."""
+        ...
+
+    def common_dir(self) -> str:
+        """The git directory shared by all worktrees, e.g. the main checkout's `.git`."""
+        ...
+
+    def config(self) -> Config:
+        """
+        The repository's configuration, layered over the global levels. Writes go to
+        `.git/config`.
+        """
+        ...
+
+    def reflog(self, ref: str = "HEAD") -> List[ReflogEntry]:
+        """
+        The reflog of a ref, most recent entry first, like `git reflog show`.
+
+        Parameters:
+            ref (str): `HEAD`, a full ref name, or a short name such as `main`.
+
+        Returns:
+            List[ReflogEntry]: The entries; empty when the ref has no reflog.
+        """
+        ...
+
+    def unreachable_commits(self, mailmap: Optional[str] = None) -> List[Commit]:
+        """
+        Every commit recorded in a reflog that no branch, tag or HEAD reaches any more,
+        newest first: the work lost by resets, amends, rebases and deleted branches.
+        Stashes are not counted as lost.
+
+        Parameters:
+            mailmap (Optional[str]): Path to a mailmap file to apply.
+        """
+        ...
+
+    def dangling_commits(self, mailmap: Optional[str] = None) -> List[Commit]:
+        """
+        The tips of `unreachable_commits`: those no other unreachable commit has as a
+        parent, like `git fsck --lost-found`. Point a branch at one (e.g. with
+        `update_ref`) to recover it.
+
+        Parameters:
+            mailmap (Optional[str]): Path to a mailmap file to apply.
+        """
+        ...
+
+    def update_ref(
+        self,
+        ref: str,
+        target: str,
+        message: str,
+        expected: Optional[str] = None,
+    ) -> str:
+        """
+        Point a ref at a commit, creating it if needed, and log the update, like
+        `git update-ref -m`.
+
+        Parameters:
+            ref (str): Full ref name, e.g. `refs/heads/recovered`.
+            target (str): Revision to point the ref at.
+            message (str): Reflog message for the update.
+            expected (Optional[str]): Only update if the ref still points at this revision.
+
+        Returns:
+            str: The id of the new target.
+
+        Raises:
+            OSError: If the ref no longer points at `expected`.
+        """
+        ...
+
+    def reflog_append(self, ref: str, new_id: str, message: str) -> None:
+        """
+        Append an entry for `ref` moving to `new_id` to its reflog, creating the reflog
+        if needed.
+        """
+        ...
+
     @staticmethod
     async def async_clone(
         urls: List[str],
@@ -0,0 +1,76 @@
+from typing import Any, Optional
+
+
+class GitObject:
+    """
+    A resolved Git object: a commit, tree, blob or annotated tag.
+    """
+
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, ...]: ...
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the fields as a plain dictionary, e.g. for `pandas.DataFrame`."""
+        ...
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "GitObject":
+        """Rebuild an instance from the output of `to_dict`."""
+        ...
+
+    @property
+    def id(self) -> str:
+        """Full SHA of the object."""
+        ...
+
+    @property
+    def short_id(self) -> str:
+        """Shortest unambiguous abbreviation of the SHA, honouring `core.abbrev`."""
+        ...
+
+    @property
+    def kind(self) -> str:
+        """Object type: "commit", "tree", "blob" or "tag"."""
+        ...
+
+
+class RevRange:
+    """
+    The endpoints of a revision range such as `A..B` or `A...B`.
+    """
+
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, ...]: ...
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the fields as a plain dictionary, e.g. for `pandas.DataFrame`."""
+        ...
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "RevRange":
+        """Rebuild an instance from the output of `to_dict`."""
+        ...
+
+    @property
+    def start(self) -> Optional[GitObject]:
+        """Left-hand side of the range."""
+        ...
+
+    @property
+    def end(self) -> Optional[GitObject]:
+        """Right-hand side of the range."""
+        ...
+
+    @property
+    def symmetric(self) -> bool:
+        """True for the symmetric difference `A...B`."""
+        ...
+
+    @property
+    def merge_base(self) -> Optional[str]:
+        """Merge base of the endpoints; only set for `A...B`."""
+        ...
@@ -0,0 +1,89 @@
+from typing import Any
+
+from ..commits.model import Commit, DiffHunk
+
+
+class PickaxeMatch:
+    """
+    A commit whose diff against its parent adds or removes the searched pattern.
+    """
+
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, ...]: ...
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the fields as a plain dictionary, e.g. for `pandas.DataFrame`."""
+        ...
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "PickaxeMatch":
+        """Rebuild an instance from the output of `to_dict`."""
+        ...
+
+    @property
+    def commit(self) -> Commit:
+        """The matching commit."""
+        ...
+
+    @property
+    def paths(self) -> list[str]:
+        """Files whose changes matched."""
+        ...
+
+    @property
+    def hunks(self) -> list[DiffHunk]:
+        """Hunks with a matching added or removed line. For `-S` searches where no
+        single line matches, all hunks of the matching files."""
+        ...
+
+
+class GrepMatch:
+    """
+    A line matched by `Repo.grep`.
+    """
+
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, ...]: ...
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the fields as a plain dictionary, e.g. for `pandas.DataFrame`."""
+        ...
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "GrepMatch":
+        """Rebuild an instance from the output of `to_dict`."""
+        ...
+
+    @property
+    def path(self) -> str:
+        """Path of the file relative to the repository root."""
+        ...
+
+    @property
+    def line_number(self) -> int:
+        """1-based line number of the match."""
+        ...
+
+    @property
+    def column(self) -> int:
+        """1-based column (in characters) where the first match on the line starts."""
+        ...
+
+    @property
+    def line(self) -> str:
+        """The matching line, without its line ending."""
+        ...
+
+    @property
+    def before(self) -> list[str]:
+        """Up to `context_lines` lines preceding the match."""
+        ...
+
+    @property
+    def after(self) -> list[str]:
+        """Up to `context_lines` lines following the match."""
+        ...
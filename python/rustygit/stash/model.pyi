@@ -0,0 +1,52 @@
+from datetime import datetime
+from typing import Any
+
+
+class Stash:
+    """
+    An entry of the stash list. Index 0 is the most recent entry (`stash@{0}`).
+    """
+
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, ...]: ...
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the fields as a plain dictionary, e.g. for `pandas.DataFrame`."""
+        ...
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "Stash":
+        """Rebuild an instance from the output of `to_dict`."""
+        ...
+
+    @property
+    def index(self) -> int:
+        """Position in the stash list, as in `stash@{index}`."""
+        ...
+
+    @property
+    def message(self) -> str:
+        """The stash message, e.g. "On main: wip"."""
+        ...
+
+    @property
+    def commit_id(self) -> str:
+        """Id of the stash commit."""
+        ...
+
+    @property
+    def time(self) -> int:
+        """Time the stash was saved (Unix timestamp)."""
+        ...
+
+    @property
+    def offset_minutes(self) -> int:
+        """UTC offset of `time` in minutes."""
+        ...
+
+    @property
+    def datetime(self) -> datetime:
+        """Time the stash was saved as a timezone-aware datetime."""
+        ...
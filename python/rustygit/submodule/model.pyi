@@ -0,0 +1,78 @@
+from typing import Any, Optional
+
+
+class Submodule:
+    """
+    A submodule of the repository, as configured in `.gitmodules`.
+
+    `head_id` and `index_id` are the commits recorded for it in the superproject's
+    HEAD and index; `workdir_id` is the commit checked out in its working directory.
+    """
+
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, ...]: ...
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the fields as a plain dictionary, e.g. for `pandas.DataFrame`."""
+        ...
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "Submodule":
+        """Rebuild an instance from the output of `to_dict`."""
+        ...
+
+    @property
+    def name(self) -> str:
+        """Name of the submodule in `.gitmodules`."""
+        ...
+
+    @property
+    def path(self) -> str:
+        """Path of the submodule relative to the superproject's root."""
+        ...
+
+    @property
+    def url(self) -> Optional[str]:
+        """Remote URL of the submodule."""
+        ...
+
+    @property
+    def branch(self) -> Optional[str]:
+        """Branch to track, if configured."""
+        ...
+
+    @property
+    def head_id(self) -> Optional[str]:
+        """Commit recorded for the submodule in the superproject's HEAD."""
+        ...
+
+    @property
+    def index_id(self) -> Optional[str]:
+        """Commit recorded for the submodule in the superproject's index."""
+        ...
+
+    @property
+    def workdir_id(self) -> Optional[str]:
+        """Commit checked out in the submodule; `None` when it isn't initialized."""
+        ...
+
+    @property
+    def status(self) -> list[str]:
+        """
+        Status flags: `in_head`, `in_index`, `in_config`, `in_wd`, `index_added`,
+        `index_deleted`, `index_modified`, `wd_uninitialized`, `wd_added`, `wd_deleted`,
+        `wd_modified`, `wd_index_modified`, `wd_wd_modified` and `wd_untracked`.
+        """
+        ...
+
+    @property
+    def is_initialized(self) -> bool:
+        """Whether the submodule is cloned into its working directory."""
+        ...
+
+    @property
+    def is_out_of_date(self) -> bool:
+        """Whether the checked-out commit differs from the one recorded in HEAD."""
+        ...
@@ -0,0 +1,105 @@
+from typing import Any, Optional
+
+
+class TreeEntry:
+    """
+    An entry of a Git tree: a file, a directory, or a submodule commit.
+    """
+
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, ...]: ...
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the fields as a plain dictionary, e.g. for `pandas.DataFrame`."""
+        ...
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "TreeEntry":
+        """Rebuild an instance from the output of `to_dict`."""
+        ...
+
+    @property
+    def path(self) -> str:
+        """Path of the entry relative to the repository root."""
+        ...
+
+    @property
+    def mode(self) -> int:
+        """Git file mode, e.g. 0o100644 for a regular file or 0o040000 for a tree."""
+        ...
+
+    @property
+    def kind(self) -> str:
+        """Object type of the entry: "blob", "tree" or "commit" (submodule)."""
+        ...
+
+    @property
+    def id(self) -> str:
+        """SHA of the object the entry points to."""
+        ...
+
+    @property
+    def size(self) -> Optional[int]:
+        """Size in bytes for blobs, None for other kinds."""
+        ...
+
+
+class Blob:
+    """
+    A file's content at a revision, as yielded by `Repo.iter_blobs`.
+    """
+
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, ...]: ...
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the fields as a plain dictionary, e.g. for `pandas.DataFrame`."""
+        ...
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "Blob":
+        """Rebuild an instance from the output of `to_dict`."""
+        ...
+
+    @property
+    def path(self) -> str:
+        """Path of the file relative to the repository root."""
+        ...
+
+    @property
+    def mode(self) -> int:
+        """Git file mode, e.g. 0o100644, 0o100755 or 0o120000 for a symlink."""
+        ...
+
+    @property
+    def id(self) -> str:
+        """SHA of the blob."""
+        ...
+
+    @property
+    def size(self) -> int:
+        """Size of the content in bytes."""
+        ...
+
+    @property
+    def is_binary(self) -> bool:
+        """Whether Git's heuristic (a NUL byte near the start) considers the content binary."""
+        ...
+
+    @property
+    def data(self) -> bytes:
+        """Raw content of the file."""
+        ...
+
+
+class BlobIter:
+    """
+    Iterator over the blobs selected by `Repo.iter_blobs`; each blob is read when reached.
+    """
+
+    def __iter__(self) -> "BlobIter": ...
+    def __next__(self) -> Blob: ...
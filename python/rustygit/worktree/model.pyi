@@ -0,0 +1,46 @@
+from typing import Any, Optional
+
+
+class Worktree:
+    """
+    A linked worktree of the repository, as listed by `git worktree list`.
+    """
+
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, ...]: ...
+
+    def to_dict(self) -> dict[str, Any]:
+        """Return the fields as a plain dictionary, e.g. for `pandas.DataFrame`."""
+        ...
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> "Worktree":
+        """Rebuild an instance from the output of `to_dict`."""
+        ...
+
+    @property
+    def name(self) -> str:
+        """Name of the worktree, as used by the `*_worktree` methods."""
+        ...
+
+    @property
+    def path(self) -> str:
+        """Directory the worktree is checked out in."""
+        ...
+
+    @property
+    def locked(self) -> bool:
+        """Whether the worktree is locked against pruning."""
+        ...
+
+    @property
+    def lock_reason(self) -> Optional[str]:
+        """Reason given when the worktree was locked, if any."""
+        ...
+
+    @property
+    def is_valid(self) -> bool:
+        """Whether the worktree's directory and git files are still in place; invalid worktrees can be pruned."""
+        ...
@@ -1,11 +1,20 @@
+use crate::utils::py_model;
 use pyo3::prelude::*;
 
-#[pyclass]
+#[pyclass(module = "rustygit")]
+#[derive(Clone, Debug)]
 pub struct Branch {
     name: String,
     is_remote: bool,
 }
 
+py_model!(
+    Branch,
+    fields: [name, is_remote],
+    key: [name, is_remote],
+    repr: [name, is_remote],
+);
+
 #[pymethods]
 impl Branch {
     #[new]
@@ -1,18 +1,24 @@
-use crate::utils::git_err_to_py_err;
-use git2::{BlameOptions, DiffOptions, Repository};
+use crate::repo::Repo;
+use crate::utils::{git_err_to_py_err, load_mailmap, walk_revisions};
+use git2::{BlameOptions, Delta, DiffFindOptions, DiffOptions, Mailmap, Repository};
 use pyo3::prelude::*;
-use pyo3::types::{PyDict, PyList};
 use rayon::prelude::*;
+use std::cell::RefCell;
+use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
-use std::sync::Mutex;
+use std::path::{Path, PathBuf};
 
-use super::model::{BlameLine, Commit, DiffEntry};
+use super::model::{
+    BlameError, BlameErrorKind, BlameLine, BlameResult, Commit, DiffEntry, DiffHunk,
+};
 
 #[pyfunction]
-pub fn get_commit_history(path: &str) -> PyResult<Vec<Commit>> {
+#[pyo3(signature = (path, mailmap=None))]
+pub fn get_commit_history(path: &str, mailmap: Option<&str>) -> PyResult<Vec<Commit>> {
     let repo = Repository::open(path).map_err(git_err_to_py_err)?;
+    let mailmap = load_mailmap(&repo, mailmap).map_err(git_err_to_py_err)?;
     let mut revwalk = repo.revwalk().map_err(git_err_to_py_err)?;
     revwalk.push_head().map_err(git_err_to_py_err)?;
 
@@ -20,7 +26,7 @@ pub fn get_commit_history(path: &str) -> PyResult<Vec<Commit>> {
     for oid_result in revwalk {
         let oid = oid_result.map_err(git_err_to_py_err)?;
         let commit = repo.find_commit(oid).map_err(git_err_to_py_err)?;
-        commits.push(Commit::from_git_commit(&commit));
+        commits.push(Commit::from_git_commit(&commit, Some(&mailmap)));
     }
 
     Ok(commits)
@@ -91,131 +97,215 @@ pub fn get_file_change_summary(
 }
 
 #[pyfunction]
-pub fn get_file_blame(file_path: &str) -> PyResult<Vec<BlameLine>> {
+#[pyo3(signature = (file_path, mailmap=None))]
+pub fn get_file_blame(file_path: &str, mailmap: Option<&str>) -> PyResult<Vec<BlameLine>> {
     let abs_path = std::fs::canonicalize(file_path)
         .map_err(|e| PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("{}", e)))?;
     let repo = Repository::discover(&abs_path).map_err(git_err_to_py_err)?;
+    let mailmap = load_mailmap(&repo, mailmap).map_err(git_err_to_py_err)?;
 
-    let rel_path = get_repository_relative_path(&abs_path, &repo)
-        .map_err(|e| {
-            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
-                "Failed to get relative path: {}",
-                e
-            ))
-        })?;
-
-    let mut options = BlameOptions::new();
-    let blame = repo
-        .blame_file(&rel_path, Some(&mut options))
-        .map_err(git_err_to_py_err)?;
-
-    let file = File::open(&abs_path)
-        .map_err(|e| PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("{}", e)))?;
-    let lines = BufReader::new(file)
-        .lines()
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("{}", e)))?;
+    blame_lines(&repo, &mailmap, &abs_path).map_err(PyErr::from)
+}
 
-    let mut result = Vec::new();
-    for (i, line) in lines.iter().enumerate() {
-        if let Some(hunk) = blame.get_line(i + 1) {
-            let commit = repo
-                .find_commit(hunk.final_commit_id())
-                .map_err(git_err_to_py_err)?;
-            let summary = commit.summary().unwrap_or("").to_string();
+#[pyfunction]
+#[pyo3(signature = (file_paths, mailmap=None))]
+pub fn get_blame_for_files(
+    py: Python,
+    file_paths: Vec<String>,
+    mailmap: Option<&str>,
+) -> PyResult<BlameResult> {
+    // Validate supplied mailmap text once rather than failing every file.
+    if let Some(text) = mailmap {
+        Mailmap::from_buffer(text).map_err(git_err_to_py_err)?;
+    }
+    Ok(py.allow_threads(|| blame_files(&file_paths, mailmap)))
+}
 
-            result.push(BlameLine::new(
-                i + 1,
-                line.clone(),
-                hunk.final_commit_id().to_string(),
-                hunk.final_signature().name().unwrap_or("").to_string(),
-                hunk.final_signature().email().unwrap_or("").to_string(),
-                hunk.final_signature().when().seconds(),
-                hunk.orig_signature().name().unwrap_or("").to_string(),
-                hunk.orig_signature().when().seconds(),
-                summary,
-            ));
+/// Blame many files, possibly spread over several repositories.
+///
+/// Files are grouped by the repository that contains them and each worker
+/// thread opens a given repository at most once.
+pub(crate) fn blame_files(file_paths: &[String], mailmap: Option<&str>) -> BlameResult {
+    let mut errors = Vec::new();
+    let mut targets = Vec::new();
+    for (input, located) in file_paths
+        .par_iter()
+        .map(|p| (p, locate_blame_target(p)))
+        .collect::<Vec<_>>()
+    {
+        match located {
+            Ok(target) => targets.push(target),
+            Err((kind, message)) => errors.push(BlameError::new(input.clone(), kind, message)),
         }
     }
 
-    Ok(result)
-}
-
-#[pyfunction]
-pub fn get_blame_for_files(py: Python, file_paths: Vec<String>) -> PyResult<PyObject> {
-    let results: Mutex<HashMap<String, Vec<BlameLine>>> = Mutex::new(HashMap::new());
-    let errors: Mutex<Vec<(String, String)>> = Mutex::new(Vec::new());
+    // Keep files from the same repository next to each other so that rayon's
+    // splits mostly hand a worker files from a single repository.
+    targets.sort_by(|a, b| a.git_dir.cmp(&b.git_dir));
 
-    file_paths
+    let outcomes: Vec<_> = targets
         .par_iter()
-        .for_each(|file_path| match process_file_blame(file_path) {
-            Ok(entries) => {
-                results.lock().unwrap().insert(file_path.clone(), entries);
+        .map(|target| {
+            let lines =
+                with_cached_repo(&target.git_dir, mailmap, |repo, mailmap| {
+                    blame_lines(repo, mailmap, &target.abs_path)
+                });
+            (target, lines)
+        })
+        .collect();
+    rayon::broadcast(|_| REPO_CACHE.with(|cache| cache.borrow_mut().clear()));
+
+    let mut results = HashMap::new();
+    for (target, lines) in outcomes {
+        match lines {
+            Ok(lines) => {
+                results.insert(target.input.clone(), lines);
             }
-            Err(error_msg) => {
-                errors.lock().unwrap().push((file_path.clone(), error_msg));
+            Err(failure) => {
+                let (kind, message) = failure.describe();
+                errors.push(BlameError::new(target.input.clone(), kind, message));
             }
-        });
-
-    let error_list = errors.lock().unwrap();
-    if !error_list.is_empty() {
-        eprintln!("WARNING: Some files could not be processed for blame:");
-        for (file, error) in error_list.iter() {
-            eprintln!("  - {}: {}", file, error);
         }
     }
 
-    let pydict = PyDict::new(py);
-    for (k, v) in results.lock().unwrap().iter() {
-        let items: Vec<PyObject> = v.iter().map(|line| line.clone().into_py(py)).collect();
-        let py_list = PyList::new(py, &items);
-        pydict.set_item(k, py_list)?;
-    }
+    let order: HashMap<&String, usize> =
+        file_paths.iter().enumerate().map(|(i, p)| (p, i)).collect();
+    errors.sort_by_key(|e| order.get(&e.path).copied());
 
-    Ok(pydict.to_object(py))
+    BlameResult { results, errors }
 }
 
-fn process_file_blame(file_path: &str) -> Result<Vec<BlameLine>, String> {
-    let abs_path = std::fs::canonicalize(file_path)
-        .map_err(|e| format!("Failed to canonicalize path: {}", e))?;
+struct BlameTarget {
+    input: String,
+    git_dir: PathBuf,
+    abs_path: PathBuf,
+}
 
-    let repo = Repository::discover(&abs_path)
-        .map_err(|e| format!("Failed to discover repository: {}", e))?;
+/// Resolve `file_path` and find the repository that contains it without opening it.
+fn locate_blame_target(file_path: &str) -> Result<BlameTarget, (BlameErrorKind, String)> {
+    let abs_path = std::fs::canonicalize(file_path).map_err(|e| {
+        (
+            BlameErrorKind::NotFound,
+            format!("Failed to canonicalize path: {}", e),
+        )
+    })?;
+    let git_dir =
+        Repository::discover_path(&abs_path, std::iter::empty::<&str>()).map_err(|e| {
+            (
+                BlameErrorKind::NotInRepository,
+                format!("Failed to discover repository: {}", e),
+            )
+        })?;
 
-    let rel_path = get_repository_relative_path(&abs_path, &repo)
-        .map_err(|e| format!("Failed to get relative path: {}", e))?;
+    Ok(BlameTarget {
+        input: file_path.to_string(),
+        git_dir,
+        abs_path,
+    })
+}
 
-    let mut options = BlameOptions::new();
+thread_local! {
+    /// Repositories (and their mailmaps) opened by the current rayon worker
+    /// during `blame_files`.
+    static REPO_CACHE: RefCell<HashMap<PathBuf, (Repository, Mailmap)>> =
+        RefCell::new(HashMap::new());
+}
 
+fn with_cached_repo<T>(
+    git_dir: &Path,
+    mailmap: Option<&str>,
+    f: impl FnOnce(&Repository, &Mailmap) -> Result<T, BlameFailure>,
+) -> Result<T, BlameFailure> {
+    REPO_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if !cache.contains_key(git_dir) {
+            let repo = Repository::open(git_dir).map_err(BlameFailure::Open)?;
+            let mailmap = load_mailmap(&repo, mailmap).map_err(BlameFailure::Git)?;
+            cache.insert(git_dir.to_path_buf(), (repo, mailmap));
+        }
+        let (repo, mailmap) = &cache[git_dir];
+        f(repo, mailmap)
+    })
+}
+
+/// Internal error for a single file's blame, convertible both to a Python
+/// exception and to a `BlameError` entry.
+enum BlameFailure {
+    Open(git2::Error),
+    RelativePath(String),
+    Git(git2::Error),
+    Io(std::io::Error),
+}
+
+impl BlameFailure {
+    fn describe(&self) -> (BlameErrorKind, String) {
+        match self {
+            BlameFailure::Open(e) => (
+                BlameErrorKind::NotInRepository,
+                format!("Failed to open repository: {}", e),
+            ),
+            BlameFailure::RelativePath(e) => (
+                BlameErrorKind::OutsideWorkdir,
+                format!("Failed to get relative path: {}", e),
+            ),
+            BlameFailure::Git(e) => (BlameErrorKind::Git, format!("Failed to blame file: {}", e)),
+            BlameFailure::Io(e) => (BlameErrorKind::Io, format!("Failed to read file: {}", e)),
+        }
+    }
+}
+
+impl From<BlameFailure> for PyErr {
+    fn from(failure: BlameFailure) -> PyErr {
+        match failure {
+            BlameFailure::Open(e) | BlameFailure::Git(e) => git_err_to_py_err(e),
+            BlameFailure::RelativePath(e) => PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                format!("Failed to get relative path: {}", e),
+            ),
+            BlameFailure::Io(e) => PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("{}", e)),
+        }
+    }
+}
+
+/// Blame the working-tree file at `abs_path` line by line.
+fn blame_lines(
+    repo: &Repository,
+    mailmap: &Mailmap,
+    abs_path: &Path,
+) -> Result<Vec<BlameLine>, BlameFailure> {
+    let rel_path =
+        get_repository_relative_path(abs_path, repo).map_err(BlameFailure::RelativePath)?;
+
+    let mut options = BlameOptions::new();
     let blame = repo
         .blame_file(&rel_path, Some(&mut options))
-        .map_err(|e| format!("Failed to blame file: {}", e))?;
-
-    let file = File::open(&abs_path).map_err(|e| format!("Failed to open file: {}", e))?;
+        .map_err(BlameFailure::Git)?;
 
+    let file = File::open(abs_path).map_err(BlameFailure::Io)?;
     let lines = BufReader::new(file)
         .lines()
         .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| format!("Failed to read lines: {}", e))?;
+        .map_err(BlameFailure::Io)?;
 
+    let mut summaries: HashMap<git2::Oid, String> = HashMap::new();
     let mut entries = Vec::new();
     for (i, line) in lines.iter().enumerate() {
         if let Some(hunk) = blame.get_line(i + 1) {
-            let commit = repo
-                .find_commit(hunk.final_commit_id())
-                .map_err(|e| format!("Failed to find commit: {}", e))?;
-
-            let summary = commit.summary().unwrap_or("").to_string();
+            let commit_id = hunk.final_commit_id();
+            let summary = match summaries.entry(commit_id) {
+                Entry::Occupied(entry) => entry.get().clone(),
+                Entry::Vacant(entry) => {
+                    let commit = repo.find_commit(commit_id).map_err(BlameFailure::Git)?;
+                    entry
+                        .insert(commit.summary().unwrap_or("").to_string())
+                        .clone()
+                }
+            };
 
-            entries.push(BlameLine::new(
+            entries.push(BlameLine::from_hunk(
                 i + 1,
                 line.clone(),
-                hunk.final_commit_id().to_string(),
-                hunk.final_signature().name().unwrap_or("").to_string(),
-                hunk.final_signature().email().unwrap_or("").to_string(),
-                hunk.final_signature().when().seconds(),
-                hunk.orig_signature().name().unwrap_or("").to_string(),
-                hunk.orig_signature().when().seconds(),
+                &hunk,
+                Some(mailmap),
                 summary,
             ));
         }
@@ -281,4 +371,375 @@ fn get_repository_relative_path(abs_path: &std::path::Path, repo: &Repository) -
             abs_path.display(), repo_path_buf.display()
         ))
     }
-}
\ No newline at end of file
+}
+
+/// Per-file line statistics for every delta in `diff`.
+pub fn diff_entries(diff: &git2::Diff) -> Result<Vec<DiffEntry>, git2::Error> {
+    let mut entries = Vec::with_capacity(diff.deltas().len());
+    for (idx, delta) in diff.deltas().enumerate() {
+        let path = delta
+            .new_file()
+            .path()
+            .or_else(|| delta.old_file().path())
+            .and_then(|p| p.to_str())
+            .unwrap_or("")
+            .to_string();
+        let (additions, deletions) = match git2::Patch::from_diff(diff, idx)? {
+            Some(patch) => {
+                let (_, additions, deletions) = patch.line_stats()?;
+                (additions, deletions)
+            }
+            None => (0, 0),
+        };
+        entries.push(DiffEntry::new(path, additions, deletions));
+    }
+    Ok(entries)
+}
+
+/// The hunks of `patch`, attributed to `path`.
+pub fn diff_hunks(patch: &git2::Patch, path: &str) -> Result<Vec<DiffHunk>, git2::Error> {
+    let mut hunks = Vec::with_capacity(patch.num_hunks());
+    for h in 0..patch.num_hunks() {
+        let (hunk, line_count) = patch.hunk(h)?;
+        let mut lines = Vec::with_capacity(line_count);
+        for l in 0..line_count {
+            let line = patch.line_in_hunk(h, l)?;
+            let content = String::from_utf8_lossy(line.content());
+            lines.push(format!(
+                "{}{}",
+                line.origin(),
+                content.trim_end_matches(['\n', '\r'])
+            ));
+        }
+        hunks.push(DiffHunk {
+            path: path.to_string(),
+            header: String::from_utf8_lossy(hunk.header())
+                .trim_end()
+                .to_string(),
+            old_start: hunk.old_start(),
+            old_lines: hunk.old_lines(),
+            new_start: hunk.new_start(),
+            new_lines: hunk.new_lines(),
+            lines,
+        });
+    }
+    Ok(hunks)
+}
+
+/// One commit in the history of a file followed across renames.
+pub struct FileRevision {
+    pub commit: Commit,
+    pub old_path: Option<String>,
+    pub new_path: Option<String>,
+    pub change: DiffEntry,
+}
+
+/// Every commit reachable from `rev` that changed `path`, newest first,
+/// following the file back through renames like `git log --follow`.
+///
+/// Each commit is compared with its first parent. Merges whose version of
+/// the file matches one of their parents are skipped.
+pub fn follow_file(
+    repo: &Repository,
+    path: &str,
+    rev: Option<&str>,
+    mailmap: &Mailmap,
+) -> Result<Vec<FileRevision>, git2::Error> {
+    let mut current = path.trim_matches('/').to_string();
+    let mut history = Vec::new();
+
+    for oid in walk_revisions(repo, rev)? {
+        let commit = repo.find_commit(oid)?;
+        let tree = commit.tree()?;
+        let here = blob_at(&tree, &current);
+        let parents = commit.parents().collect::<Vec<_>>();
+
+        let mut parent_trees = Vec::with_capacity(parents.len());
+        for parent in &parents {
+            parent_trees.push(parent.tree()?);
+        }
+        if parent_trees
+            .iter()
+            .any(|parent_tree| blob_at(parent_tree, &current) == here)
+        {
+            continue;
+        }
+        if here.is_none() && parents.len() > 1 {
+            continue;
+        }
+
+        let mut diff = repo.diff_tree_to_tree(parent_trees.first(), Some(&tree), None)?;
+        diff.find_similar(Some(DiffFindOptions::new().renames(true)))?;
+
+        let found = diff.deltas().enumerate().find(|(_, delta)| {
+            let new_path = delta.new_file().path().and_then(|p| p.to_str());
+            let old_path = delta.old_file().path().and_then(|p| p.to_str());
+            match delta.status() {
+                Delta::Deleted => old_path == Some(current.as_str()),
+                _ => new_path == Some(current.as_str()),
+            }
+        });
+        let Some((idx, delta)) = found else {
+            continue;
+        };
+
+        let old_path = match delta.status() {
+            Delta::Added => None,
+            _ => delta
+                .old_file()
+                .path()
+                .and_then(|p| p.to_str())
+                .map(String::from),
+        };
+        let new_path = match delta.status() {
+            Delta::Deleted => None,
+            _ => Some(current.clone()),
+        };
+        let (additions, deletions) = match git2::Patch::from_diff(&diff, idx)? {
+            Some(patch) => {
+                let (_, additions, deletions) = patch.line_stats()?;
+                (additions, deletions)
+            }
+            None => (0, 0),
+        };
+        let status = delta.status();
+
+        history.push(FileRevision {
+            commit: Commit::from_git_commit(&commit, Some(mailmap)),
+            change: DiffEntry::new(current.clone(), additions, deletions),
+            old_path: old_path.clone(),
+            new_path,
+        });
+
+        // Like `git log --follow`, keep walking past an addition: an older
+        // commit may have had a file at the same path before it was removed.
+        if matches!(status, Delta::Renamed | Delta::Copied) {
+            current = old_path.unwrap_or(current);
+        }
+    }
+
+    Ok(history)
+}
+
+/// Id of the blob at `path` in `tree`, if there is one.
+fn blob_at(tree: &git2::Tree, path: &str) -> Option<git2::Oid> {
+    tree.get_path(Path::new(path))
+        .ok()
+        .filter(|entry| entry.kind() == Some(git2::ObjectType::Blob))
+        .map(|entry| entry.id())
+}
+
+/// `(commit, old_path, new_path, stats)` as returned to Python.
+type FileHistoryRecord = (Commit, Option<String>, Option<String>, DiffEntry);
+
+#[pymethods]
+impl Repo {
+    /// Commits that changed `path`, following renames like `git log --follow`.
+    ///
+    /// Returns `(Commit, old_path, new_path, DiffEntry)` tuples, newest first.
+    #[pyo3(signature = (path, rev=None, mailmap=None))]
+    fn file_history(
+        &self,
+        py: Python<'_>,
+        path: &str,
+        rev: Option<&str>,
+        mailmap: Option<&str>,
+    ) -> PyResult<Vec<FileHistoryRecord>> {
+        let git_dir = self.inner.path().to_path_buf();
+        let history = py
+            .allow_threads(|| {
+                let repo = Repository::open(&git_dir)?;
+                let mailmap = load_mailmap(&repo, mailmap)?;
+                follow_file(&repo, path, rev, &mailmap)
+            })
+            .map_err(git_err_to_py_err)?;
+
+        Ok(history
+            .into_iter()
+            .map(|r| (r.commit, r.old_path, r.new_path, r.change))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestRepo;
+
+    const LINES: &str = "one\ntwo\nthree\nfour\nfive\nsix\nseven\neight\n";
+
+    /// Commit the index after applying `edit` to it, on top of `HEAD`.
+    fn commit_index_with(t: &TestRepo, message: &str, edit: impl FnOnce(&mut git2::Index)) {
+        let repo = t.git();
+        let mut index = repo.index().unwrap();
+        edit(&mut index);
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = repo.signature().unwrap();
+        let parent = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &[&parent],
+        )
+        .unwrap();
+    }
+
+    fn rename(t: &TestRepo, from: &str, to: &str, message: &str) {
+        std::fs::rename(t.path().join(from), t.path().join(to)).unwrap();
+        commit_index_with(t, message, |index| {
+            index.remove_path(Path::new(from)).unwrap();
+            index.add_path(Path::new(to)).unwrap();
+        });
+    }
+
+    fn remove(t: &TestRepo, path: &str, message: &str) {
+        std::fs::remove_file(t.path().join(path)).unwrap();
+        commit_index_with(t, message, |index| {
+            index.remove_path(Path::new(path)).unwrap();
+        });
+    }
+
+    fn follow(t: &TestRepo, path: &str) -> Vec<(String, Option<String>, Option<String>)> {
+        let mailmap = Mailmap::new().unwrap();
+        follow_file(t.git(), path, None, &mailmap)
+            .unwrap()
+            .into_iter()
+            .map(|r| {
+                let subject = r.commit.message.trim().to_string();
+                (subject, r.old_path, r.new_path)
+            })
+            .collect()
+    }
+
+    fn step(
+        subject: &str,
+        old: Option<&str>,
+        new: Option<&str>,
+    ) -> (String, Option<String>, Option<String>) {
+        (
+            subject.to_string(),
+            old.map(str::to_string),
+            new.map(str::to_string),
+        )
+    }
+
+    #[test]
+    fn blame_files_spans_repositories_and_reports_failures() {
+        let first = TestRepo::new();
+        let a = first.commit("a.txt", "one\ntwo\n", "add a");
+        first.write("untracked.txt", "new\n");
+        let second = TestRepo::new();
+        let b = second.commit("b.txt", "three\n", "add b");
+        std::fs::write(first.sibling("loose.txt"), "loose\n").unwrap();
+
+        let path = |t: &TestRepo, name: &str| t.path().join(name).to_string_lossy().into_owned();
+        let inputs = vec![
+            path(&first, "a.txt"),
+            path(&first, "missing.txt"),
+            path(&second, "b.txt"),
+            path(&first, "untracked.txt"),
+            first.sibling("loose.txt").to_string_lossy().into_owned(),
+        ];
+        let result = blame_files(&inputs, None);
+
+        assert_eq!(result.results.len(), 2);
+        let a_lines = &result.results[&inputs[0]];
+        assert_eq!(a_lines.len(), 2);
+        assert_eq!(a_lines[1].line_number, 2);
+        assert_eq!(a_lines[1].content, "two");
+        assert_eq!(a_lines[1].commit_hash, a.to_string());
+        assert_eq!(a_lines[1].summary, "add a");
+        assert_eq!(result.results[&inputs[2]][0].commit_hash, b.to_string());
+
+        let errors: Vec<(&str, BlameErrorKind)> = result
+            .errors
+            .iter()
+            .map(|e| (e.path.as_str(), e.kind))
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                (inputs[1].as_str(), BlameErrorKind::NotFound),
+                (inputs[3].as_str(), BlameErrorKind::Git),
+                (inputs[4].as_str(), BlameErrorKind::NotInRepository),
+            ]
+        );
+    }
+
+    #[test]
+    fn blame_files_uses_the_given_mailmap() {
+        let t = TestRepo::new();
+        t.commit("a.txt", "one\n", "add a");
+        let input = t.path().join("a.txt").to_string_lossy().into_owned();
+        let mailmap = "Canonical <canonical@example.com> <test@example.com>\n";
+
+        let result = blame_files(std::slice::from_ref(&input), Some(mailmap));
+        let line = &result.results[&input][0];
+        assert_eq!(line.author, "Canonical");
+        assert_eq!(line.author_email, "canonical@example.com");
+        assert_eq!(line.raw_author, "Test User");
+        assert!(result.errors.is_empty());
+    }
+
+    #[test]
+    fn follow_file_crosses_renames() {
+        let t = TestRepo::new();
+        t.commit("a.txt", LINES, "add a");
+        t.commit("other.txt", "x\n", "unrelated");
+        t.commit("a.txt", &format!("{}nine\n", LINES), "edit a");
+        rename(&t, "a.txt", "b.txt", "rename to b");
+        t.commit("b.txt", &format!("zero\n{}nine\n", LINES), "edit b");
+
+        assert_eq!(
+            follow(&t, "b.txt"),
+            vec![
+                step("edit b", Some("b.txt"), Some("b.txt")),
+                step("rename to b", Some("a.txt"), Some("b.txt")),
+                step("edit a", Some("a.txt"), Some("a.txt")),
+                step("add a", None, Some("a.txt")),
+            ]
+        );
+    }
+
+    #[test]
+    fn follow_file_walks_past_an_addition() {
+        let t = TestRepo::new();
+        t.commit("a.txt", LINES, "add a");
+        remove(&t, "a.txt", "remove a");
+        t.commit("a.txt", "new\n", "add a again");
+        t.commit("a.txt", "newer\n", "edit a");
+
+        assert_eq!(
+            follow(&t, "a.txt"),
+            vec![
+                step("edit a", Some("a.txt"), Some("a.txt")),
+                step("add a again", None, Some("a.txt")),
+                step("remove a", Some("a.txt"), None),
+                step("add a", None, Some("a.txt")),
+            ]
+        );
+    }
+
+    #[test]
+    fn follow_file_then_follows_the_older_name() {
+        let t = TestRepo::new();
+        t.commit("a.txt", LINES, "add a");
+        rename(&t, "a.txt", "b.txt", "rename to b");
+        remove(&t, "b.txt", "remove b");
+        t.commit("b.txt", "fresh\n", "add b again");
+
+        assert_eq!(
+            follow(&t, "b.txt"),
+            vec![
+                step("add b again", None, Some("b.txt")),
+                step("remove b", Some("b.txt"), None),
+                step("rename to b", Some("a.txt"), Some("b.txt")),
+                step("add a", None, Some("a.txt")),
+            ]
+        );
+    }
+}
@@ -0,0 +1,329 @@
+//! Parsing of commit messages into subject, body, trailers and
+//! Conventional Commits headers.
+
+/// Prefixes that mark a line as written by git itself. A trailer block that
+/// contains one of these only needs 25% of its lines to be trailers.
+const GIT_GENERATED_PREFIXES: [&str; 2] = ["Signed-off-by: ", "(cherry picked from commit "];
+
+/// Split a message into its subject and body.
+///
+/// The subject is the first paragraph with line breaks folded into spaces,
+/// matching `git log --format=%s`; the body is everything after it.
+pub fn split_message(message: &str) -> (String, String) {
+    let message = message.trim_start_matches(['\n', '\r']);
+    let lines: Vec<&str> = message.lines().collect();
+    let subject_end = lines
+        .iter()
+        .position(|line| line.trim().is_empty())
+        .unwrap_or(lines.len());
+
+    let subject = lines[..subject_end]
+        .iter()
+        .map(|line| line.trim())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let body = lines[subject_end..]
+        .iter()
+        .skip_while(|line| line.trim().is_empty())
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .to_string();
+
+    (subject, body)
+}
+
+/// Extract `(key, value)` trailers from the last paragraph of a message,
+/// following the rules of `git interpret-trailers`.
+///
+/// The last paragraph is a trailer block when it is not the subject and
+/// either every line is a trailer (or a continuation of one), or at least a
+/// quarter of its lines are trailers and one of them was generated by git.
+pub fn parse_trailers(message: &str) -> Vec<(String, String)> {
+    let lines: Vec<&str> = message.trim_end().lines().collect();
+    let block_start = match lines.iter().rposition(|line| line.trim().is_empty()) {
+        Some(blank) => blank + 1,
+        // A single paragraph is only a subject.
+        None => return Vec::new(),
+    };
+    if lines[..block_start]
+        .iter()
+        .all(|line| line.trim().is_empty())
+    {
+        return Vec::new();
+    }
+
+    let mut trailers: Vec<(String, String)> = Vec::new();
+    let mut trailer_lines = 0;
+    let mut other_lines = 0;
+    let mut git_generated = false;
+    let mut after_trailer = false;
+    for line in &lines[block_start..] {
+        if line.starts_with([' ', '\t']) {
+            // A continuation belongs to the line right above it: it extends
+            // a trailer's value, or counts with the non-trailer it follows.
+            if after_trailer {
+                let (_, value) = trailers.last_mut().unwrap();
+                value.push(' ');
+                value.push_str(line.trim());
+            } else {
+                other_lines += 1;
+            }
+            continue;
+        }
+        if GIT_GENERATED_PREFIXES.iter().any(|p| line.starts_with(p)) {
+            git_generated = true;
+        }
+        match split_trailer(line) {
+            Some((key, value)) => {
+                trailer_lines += 1;
+                trailers.push((key, value));
+                after_trailer = true;
+            }
+            None => {
+                other_lines += 1;
+                after_trailer = false;
+            }
+        }
+    }
+
+    let all_trailers = trailer_lines > 0 && other_lines == 0;
+    let mostly_git = git_generated && trailer_lines * 3 >= other_lines;
+    if all_trailers || mostly_git {
+        trailers
+    } else {
+        Vec::new()
+    }
+}
+
+/// Split a `Key: value` line. Keys are made of alphanumerics and dashes, as
+/// git requires.
+fn split_trailer(line: &str) -> Option<(String, String)> {
+    let (key, value) = line.split_once(':')?;
+    let key = key.trim_end();
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return None;
+    }
+    Some((key.to_string(), value.trim().to_string()))
+}
+
+/// Parse a `Name <email>` identity as found in `Co-authored-by` trailers.
+pub fn parse_identity(value: &str) -> Option<(String, String)> {
+    let (name, rest) = value.split_once('<')?;
+    let (email, _) = rest.split_once('>')?;
+    Some((name.trim().to_string(), email.trim().to_string()))
+}
+
+/// Components of a Conventional Commits header.
+pub struct ConventionalHeader {
+    pub kind: String,
+    pub scope: Option<String>,
+    pub breaking: bool,
+    pub description: String,
+}
+
+/// Parse `type(scope)!: description` from a subject line.
+///
+/// A `BREAKING CHANGE:` or `BREAKING-CHANGE:` footer in the body also marks
+/// the commit as breaking.
+pub fn parse_conventional(subject: &str, body: &str) -> Option<ConventionalHeader> {
+    let (header, description) = subject.split_once(": ")?;
+    let description = description.trim();
+    if description.is_empty() {
+        return None;
+    }
+
+    let (header, mut breaking) = match header.strip_suffix('!') {
+        Some(header) => (header, true),
+        None => (header, false),
+    };
+    let (kind, scope) = match header.split_once('(') {
+        Some((kind, scope)) => {
+            let scope = scope.strip_suffix(')')?;
+            if scope.is_empty() || scope.contains(['(', ')']) {
+                return None;
+            }
+            (kind, Some(scope.to_string()))
+        }
+        None => (header, None),
+    };
+    if kind.is_empty() || !kind.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return None;
+    }
+
+    breaking |= body
+        .lines()
+        .any(|line| line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:"));
+
+    Some(ConventionalHeader {
+        kind: kind.to_string(),
+        scope,
+        breaking,
+        description: description.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(trailers: &[(&str, &str)]) -> Vec<(String, String)> {
+        trailers
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn split_message_cases() {
+        let cases = [
+            ("", "", ""),
+            ("Subject", "Subject", ""),
+            ("Subject\n", "Subject", ""),
+            ("\n\nSubject\n\n\nBody\n", "Subject", "Body"),
+            (
+                "Subject\nwrapped\n\nBody\n\nMore\n",
+                "Subject wrapped",
+                "Body\n\nMore",
+            ),
+            ("Subject\r\n\r\nBody\r\n", "Subject", "Body"),
+        ];
+        for (message, subject, body) in cases {
+            assert_eq!(
+                split_message(message),
+                (subject.to_string(), body.to_string()),
+                "{:?}",
+                message
+            );
+        }
+    }
+
+    #[test]
+    fn parse_trailers_cases() {
+        let cases: &[(&str, &[(&str, &str)])] = &[
+            // A subject on its own is never a trailer block.
+            ("Signed-off-by: A <a@example.com>", &[]),
+            ("Subject\n", &[]),
+            (
+                "Subject\n\nSigned-off-by: A <a@example.com>\nFixes: #12\n",
+                &[("Signed-off-by", "A <a@example.com>"), ("Fixes", "#12")],
+            ),
+            // Only the last paragraph counts.
+            ("Subject\n\nFixes: #1\n\nBody text.\n", &[]),
+            ("Subject\n\nNot a key: value\n", &[]),
+            // Without a git-generated line every line must be a trailer.
+            (
+                "Subject\n\nCo-authored-by: B <b@example.com>\nthanks B\n",
+                &[],
+            ),
+            // With one, a quarter of the lines being trailers is enough...
+            (
+                "Subject\n\none\ntwo\nthree\nSigned-off-by: A <a@example.com>\n",
+                &[("Signed-off-by", "A <a@example.com>")],
+            ),
+            (
+                "Subject\n\n(cherry picked from commit abc)\none\ntwo\nFixes: #3\n",
+                &[("Fixes", "#3")],
+            ),
+            // ...but not less.
+            (
+                "Subject\n\none\ntwo\nthree\nfour\nSigned-off-by: A <a@example.com>\n",
+                &[],
+            ),
+            // Continuation lines extend the trailer right above them.
+            (
+                "Subject\n\nReviewed-by: A\n  and B\n\tand C\nFixes: #1\n",
+                &[("Reviewed-by", "A and B and C"), ("Fixes", "#1")],
+            ),
+            // After a non-trailer they belong to it, not to an earlier trailer.
+            (
+                "Subject\n\nSigned-off-by: A <a@example.com>\nnot a trailer\n  continued\n",
+                &[("Signed-off-by", "A <a@example.com>")],
+            ),
+            ("Subject\n\nFixes: #1\nnot a trailer\n  continued\n", &[]),
+        ];
+        for (message, expected) in cases {
+            assert_eq!(parse_trailers(message), pairs(expected), "{:?}", message);
+        }
+    }
+
+    #[test]
+    fn parse_identity_cases() {
+        assert_eq!(
+            parse_identity("Jane Doe <jane@example.com>"),
+            Some(("Jane Doe".to_string(), "jane@example.com".to_string()))
+        );
+        assert_eq!(parse_identity("Jane Doe"), None);
+        assert_eq!(parse_identity("Jane <jane@example.com"), None);
+    }
+
+    #[test]
+    fn parse_conventional_cases() {
+        type Expected = Option<(&'static str, Option<&'static str>, bool, &'static str)>;
+        let cases: &[(&str, &str, Expected)] = &[
+            (
+                "feat: add export",
+                "",
+                Some(("feat", None, false, "add export")),
+            ),
+            (
+                "fix(parser): handle tabs",
+                "",
+                Some(("fix", Some("parser"), false, "handle tabs")),
+            ),
+            (
+                "feat!: drop Python 3.7",
+                "",
+                Some(("feat", None, true, "drop Python 3.7")),
+            ),
+            (
+                "refactor(api)!: rename Repo.open",
+                "",
+                Some(("refactor", Some("api"), true, "rename Repo.open")),
+            ),
+            (
+                "chore: bump deps",
+                "Details.\n\nBREAKING CHANGE: needs Rust 1.80",
+                Some(("chore", None, true, "bump deps")),
+            ),
+            (
+                "chore: bump deps",
+                "BREAKING-CHANGE: needs Rust 1.80",
+                Some(("chore", None, true, "bump deps")),
+            ),
+            // Only a footer at the start of a line counts.
+            (
+                "docs: explain",
+                "Mentions BREAKING CHANGE: in passing",
+                Some(("docs", None, false, "explain")),
+            ),
+            ("Merge branch 'main'", "", None),
+            ("feat:missing space", "", None),
+            ("feat: ", "", None),
+            ("feat(): empty scope", "", None),
+            ("feat(a)(b): two scopes", "", None),
+            ("feat(a: unclosed", "", None),
+            ("new feature: spaces in type", "", None),
+        ];
+        for (subject, body, expected) in cases {
+            let parsed = parse_conventional(subject, body).map(|header| {
+                (
+                    header.kind,
+                    header.scope,
+                    header.breaking,
+                    header.description,
+                )
+            });
+            let expected = expected.map(|(kind, scope, breaking, description)| {
+                (
+                    kind.to_string(),
+                    scope.map(str::to_string),
+                    breaking,
+                    description.to_string(),
+                )
+            });
+            assert_eq!(parsed, expected, "{:?} / {:?}", subject, body);
+        }
+    }
+}
@@ -1,6 +1,10 @@
 pub mod logic;
+pub mod message;
 pub mod model;
 
 pub use logic::{get_blame_for_files, get_commit_history, get_file_blame, get_file_change_summary};
 
-pub use model::{BlameLine, Commit, DiffEntry};
+pub use model::{
+    BlameError, BlameErrorKind, BlameLine, BlameResult, Commit, ConventionalCommit, DiffEntry,
+    DiffHunk, Identity, Trailer,
+};
@@ -1,7 +1,16 @@
+use super::logic::diff_entries;
+use super::message::{parse_conventional, parse_identity, parse_trailers, split_message};
+use crate::repo::Repo;
+use crate::tree::{tree_entries, TreeEntry};
+use crate::utils::{
+    git_err_to_py_err, load_mailmap, mailmapped_identity, py_model, to_py_datetime,
+};
 use git2::Commit as GitCommit;
+use git2::{BlameHunk, Diff, Mailmap, Oid, Signature};
 use pyo3::prelude::*;
+use std::collections::HashMap;
 
-#[pyclass]
+#[pyclass(module = "rustygit")]
 #[derive(Clone, Debug)]
 pub struct Commit {
     #[pyo3(get)]
@@ -13,34 +22,300 @@ pub struct Commit {
     #[pyo3(get)]
     pub author_time: i64,
     #[pyo3(get)]
+    pub author_offset_minutes: i32,
+    #[pyo3(get)]
     pub committer: String,
     #[pyo3(get)]
     pub committer_email: String,
     #[pyo3(get)]
     pub commit_time: i64,
     #[pyo3(get)]
+    pub commit_offset_minutes: i32,
+    #[pyo3(get)]
     pub message: String,
     #[pyo3(get)]
     pub parents: Vec<String>,
+    #[pyo3(get)]
+    pub raw_author: String,
+    #[pyo3(get)]
+    pub raw_author_email: String,
+    #[pyo3(get)]
+    pub raw_committer: String,
+    #[pyo3(get)]
+    pub raw_committer_email: String,
+    #[pyo3(get)]
+    pub co_authors: Vec<Identity>,
+    #[pyo3(get)]
+    pub tree_id: String,
+    #[pyo3(get)]
+    pub encoding: Option<String>,
+    #[pyo3(get)]
+    pub is_signed: bool,
+    #[pyo3(get)]
+    pub signature_format: Option<String>,
+    #[pyo3(get)]
+    pub raw_header: String,
+}
+
+py_model!(
+    Commit,
+    fields: [
+        hash, author, author_email, author_time, author_offset_minutes, committer,
+        committer_email, commit_time, commit_offset_minutes, message, parents, raw_author,
+        raw_author_email, raw_committer, raw_committer_email, co_authors, tree_id, encoding,
+        is_signed, signature_format, raw_header
+    ],
+    key: [hash],
+    repr: [hash, author, commit_time],
+);
+
+#[pymethods]
+impl Commit {
+    /// Author time as a timezone-aware `datetime` in the author's UTC offset
+    #[getter]
+    fn author_datetime(&self, py: Python) -> PyResult<PyObject> {
+        to_py_datetime(py, self.author_time, self.author_offset_minutes)
+    }
+
+    /// Commit time as a timezone-aware `datetime` in the committer's UTC offset
+    #[getter]
+    fn commit_datetime(&self, py: Python) -> PyResult<PyObject> {
+        to_py_datetime(py, self.commit_time, self.commit_offset_minutes)
+    }
+
+    /// First paragraph of the message, folded onto one line
+    #[getter]
+    fn subject(&self) -> String {
+        split_message(&self.message).0
+    }
+
+    /// Message after the subject paragraph, including any trailers
+    #[getter]
+    fn body(&self) -> String {
+        split_message(&self.message).1
+    }
+
+    /// Trailers from the message's final paragraph, in order
+    #[getter]
+    fn trailers(&self) -> Vec<Trailer> {
+        parse_trailers(&self.message)
+            .into_iter()
+            .map(|(key, value)| Trailer { key, value })
+            .collect()
+    }
+
+    /// Conventional Commits view of the subject, or `None` if it doesn't follow the format
+    #[getter]
+    fn conventional(&self) -> Option<ConventionalCommit> {
+        let (subject, body) = split_message(&self.message);
+        parse_conventional(&subject, &body).map(|header| ConventionalCommit {
+            r#type: header.kind,
+            scope: header.scope,
+            breaking: header.breaking,
+            description: header.description,
+        })
+    }
+
+    /// Load the parent commits from `repo` (the `parents` property holds their hashes),
+    /// canonicalizing identities through `mailmap` or the repository's own mailmap
+    #[pyo3(signature = (repo, mailmap=None))]
+    fn parent_commits(&self, repo: PyRef<Repo>, mailmap: Option<&str>) -> PyResult<Vec<Commit>> {
+        let commit = self.find_in(&repo)?;
+        let mailmap = load_mailmap(&repo.inner, mailmap).map_err(git_err_to_py_err)?;
+        Ok(commit
+            .parents()
+            .map(|parent| Commit::from_git_commit(&parent, Some(&mailmap)))
+            .collect())
+    }
+
+    /// List the commit's root tree from `repo`, optionally recursing into subtrees
+    #[pyo3(signature = (repo, recursive=false))]
+    fn tree(&self, repo: PyRef<Repo>, recursive: bool) -> PyResult<Vec<TreeEntry>> {
+        let tree = self.find_in(&repo)?.tree().map_err(git_err_to_py_err)?;
+        tree_entries(&repo.inner, &tree, "", recursive).map_err(git_err_to_py_err)
+    }
+
+    /// Per-file line statistics against a parent (the first by default).
+    /// Root commits are compared with the empty tree.
+    #[pyo3(signature = (repo, parent=0))]
+    fn diff_to_parent(&self, repo: PyRef<Repo>, parent: usize) -> PyResult<Vec<DiffEntry>> {
+        let diff = self.parent_diff(&repo, parent)?;
+        diff_entries(&diff).map_err(git_err_to_py_err)
+    }
+
+    /// Paths changed relative to the first parent
+    fn files_changed(&self, repo: PyRef<Repo>) -> PyResult<Vec<String>> {
+        let diff = self.parent_diff(&repo, 0)?;
+        Ok(diff
+            .deltas()
+            .filter_map(|delta| {
+                delta
+                    .new_file()
+                    .path()
+                    .or_else(|| delta.old_file().path())
+                    .and_then(|p| p.to_str())
+                    .map(String::from)
+            })
+            .collect())
+    }
 }
 
 impl Commit {
-    pub fn from_git_commit(commit: &GitCommit) -> Self {
+    fn find_in<'r>(&self, repo: &'r Repo) -> PyResult<GitCommit<'r>> {
+        let oid = Oid::from_str(&self.hash).map_err(git_err_to_py_err)?;
+        repo.inner.find_commit(oid).map_err(git_err_to_py_err)
+    }
+
+    fn parent_diff<'r>(&self, repo: &'r Repo, parent: usize) -> PyResult<Diff<'r>> {
+        let commit = self.find_in(repo)?;
+        let tree = commit.tree().map_err(git_err_to_py_err)?;
+        let parent_tree = if commit.parent_count() == 0 && parent == 0 {
+            None
+        } else {
+            let parent = commit.parent(parent).map_err(git_err_to_py_err)?;
+            Some(parent.tree().map_err(git_err_to_py_err)?)
+        };
+        repo.inner
+            .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)
+            .map_err(git_err_to_py_err)
+    }
+
+    /// Build a `Commit`, canonicalizing author and committer through `mailmap`
+    /// when one is given. The signature values as recorded are kept in the
+    /// `raw_*` fields.
+    pub fn from_git_commit(commit: &GitCommit, mailmap: Option<&Mailmap>) -> Self {
+        let author = commit.author();
+        let committer = commit.committer();
+        let raw_author = author.name().unwrap_or("").to_string();
+        let raw_author_email = author.email().unwrap_or("").to_string();
+        let raw_committer = committer.name().unwrap_or("").to_string();
+        let raw_committer_email = committer.email().unwrap_or("").to_string();
+
+        let ((author_name, author_email), (committer_name, committer_email)) = match mailmap {
+            Some(mailmap) => (
+                mailmapped_identity(mailmap, &author),
+                mailmapped_identity(mailmap, &committer),
+            ),
+            None => (
+                (raw_author.clone(), raw_author_email.clone()),
+                (raw_committer.clone(), raw_committer_email.clone()),
+            ),
+        };
+
+        let signature_format = signature_format(commit);
+
         Commit {
             hash: commit.id().to_string(),
-            author: commit.author().name().unwrap_or("").to_string(),
-            author_email: commit.author().email().unwrap_or("").to_string(),
-            author_time: commit.author().when().seconds(),
-            committer: commit.committer().name().unwrap_or("").to_string(),
-            committer_email: commit.committer().email().unwrap_or("").to_string(),
+            author: author_name,
+            author_email,
+            author_time: author.when().seconds(),
+            author_offset_minutes: author.when().offset_minutes(),
+            committer: committer_name,
+            committer_email,
             commit_time: commit.time().seconds(),
+            commit_offset_minutes: commit.time().offset_minutes(),
             message: commit.message().unwrap_or("").to_string(),
             parents: commit.parent_ids().map(|id| id.to_string()).collect(),
+            raw_author,
+            raw_author_email,
+            raw_committer,
+            raw_committer_email,
+            co_authors: co_authors(commit.message().unwrap_or(""), mailmap),
+            tree_id: commit.tree_id().to_string(),
+            encoding: commit.message_encoding().map(String::from),
+            is_signed: signature_format.is_some(),
+            signature_format,
+            raw_header: String::from_utf8_lossy(commit.raw_header_bytes()).into_owned(),
         }
     }
 }
 
-#[pyclass]
+/// Identify the kind of signature stored in a commit's `gpgsig` header, if any.
+fn signature_format(commit: &GitCommit) -> Option<String> {
+    let signature = commit
+        .header_field_bytes("gpgsig")
+        .or_else(|_| commit.header_field_bytes("gpgsig-sha256"))
+        .ok()?;
+    let signature = String::from_utf8_lossy(&signature);
+    let format = if signature.contains("BEGIN SSH SIGNATURE") {
+        "ssh"
+    } else if signature.contains("BEGIN SIGNED MESSAGE") {
+        "x509"
+    } else {
+        "openpgp"
+    };
+    Some(format.to_string())
+}
+
+/// Resolve the `Co-authored-by` trailers of `message` into identities.
+fn co_authors(message: &str, mailmap: Option<&Mailmap>) -> Vec<Identity> {
+    parse_trailers(message)
+        .into_iter()
+        .filter(|(key, _)| key.eq_ignore_ascii_case("Co-authored-by"))
+        .filter_map(|(_, value)| parse_identity(&value))
+        .map(|(name, email)| {
+            let (name, email) = match (mailmap, Signature::now(&name, &email)) {
+                (Some(mailmap), Ok(sig)) => mailmapped_identity(mailmap, &sig),
+                _ => (name, email),
+            };
+            Identity { name, email }
+        })
+        .collect()
+}
+
+#[pyclass(module = "rustygit")]
+#[derive(Clone, Debug)]
+pub struct Identity {
+    #[pyo3(get)]
+    pub name: String,
+    #[pyo3(get)]
+    pub email: String,
+}
+
+py_model!(
+    Identity,
+    fields: [name, email],
+    key: [name, email],
+    repr: [name, email],
+);
+
+#[pyclass(module = "rustygit")]
+#[derive(Clone, Debug)]
+pub struct Trailer {
+    #[pyo3(get)]
+    pub key: String,
+    #[pyo3(get)]
+    pub value: String,
+}
+
+py_model!(
+    Trailer,
+    fields: [key, value],
+    key: [key, value],
+    repr: [key, value],
+);
+
+#[pyclass(module = "rustygit")]
+#[derive(Clone, Debug)]
+pub struct ConventionalCommit {
+    #[pyo3(get)]
+    pub r#type: String,
+    #[pyo3(get)]
+    pub scope: Option<String>,
+    #[pyo3(get)]
+    pub breaking: bool,
+    #[pyo3(get)]
+    pub description: String,
+}
+
+py_model!(
+    ConventionalCommit,
+    fields: [r#type, scope, breaking, description],
+    key: [r#type, scope, breaking, description],
+    repr: [r#type, scope, breaking, description],
+);
+
+#[pyclass(module = "rustygit")]
 #[derive(Clone, Debug)]
 pub struct DiffEntry {
     #[pyo3(get)]
@@ -51,6 +326,13 @@ pub struct DiffEntry {
     pub deletions: usize,
 }
 
+py_model!(
+    DiffEntry,
+    fields: [path, additions, deletions],
+    key: [path, additions, deletions],
+    repr: [path, additions, deletions],
+);
+
 impl DiffEntry {
     pub fn new(path: String, additions: usize, deletions: usize) -> Self {
         DiffEntry {
@@ -61,7 +343,34 @@ impl DiffEntry {
     }
 }
 
-#[pyclass]
+/// One hunk of a textual diff. `lines` keep their `+`, `-` or space prefix.
+#[pyclass(module = "rustygit")]
+#[derive(Clone, Debug)]
+pub struct DiffHunk {
+    #[pyo3(get)]
+    pub path: String,
+    #[pyo3(get)]
+    pub header: String,
+    #[pyo3(get)]
+    pub old_start: u32,
+    #[pyo3(get)]
+    pub old_lines: u32,
+    #[pyo3(get)]
+    pub new_start: u32,
+    #[pyo3(get)]
+    pub new_lines: u32,
+    #[pyo3(get)]
+    pub lines: Vec<String>,
+}
+
+py_model!(
+    DiffHunk,
+    fields: [path, header, old_start, old_lines, new_start, new_lines, lines],
+    key: [path, old_start, new_start],
+    repr: [path, header],
+);
+
+#[pyclass(module = "rustygit")]
 #[derive(Clone, Debug)]
 pub struct BlameLine {
     #[pyo3(get)]
@@ -77,35 +386,269 @@ pub struct BlameLine {
     #[pyo3(get)]
     pub author_time: i64,
     #[pyo3(get)]
+    pub author_offset_minutes: i32,
+    #[pyo3(get)]
     pub committer: String,
     #[pyo3(get)]
     pub commit_time: i64,
     #[pyo3(get)]
+    pub commit_offset_minutes: i32,
+    #[pyo3(get)]
     pub summary: String,
+    #[pyo3(get)]
+    pub raw_author: String,
+    #[pyo3(get)]
+    pub raw_author_email: String,
+    #[pyo3(get)]
+    pub raw_committer: String,
+}
+
+py_model!(
+    BlameLine,
+    fields: [
+        line_number, content, commit_hash, author, author_email, author_time,
+        author_offset_minutes, committer, commit_time, commit_offset_minutes, summary,
+        raw_author, raw_author_email, raw_committer
+    ],
+    key: [commit_hash, line_number, content],
+    repr: [line_number, commit_hash, author],
+);
+
+#[pymethods]
+impl BlameLine {
+    /// Author time as a timezone-aware `datetime` in the author's UTC offset
+    #[getter]
+    fn author_datetime(&self, py: Python) -> PyResult<PyObject> {
+        to_py_datetime(py, self.author_time, self.author_offset_minutes)
+    }
+
+    /// Commit time as a timezone-aware `datetime` in the committer's UTC offset
+    #[getter]
+    fn commit_datetime(&self, py: Python) -> PyResult<PyObject> {
+        to_py_datetime(py, self.commit_time, self.commit_offset_minutes)
+    }
 }
 
 impl BlameLine {
-    pub fn new(
+    /// Build a `BlameLine` for `line_number` from the blame hunk covering it,
+    /// canonicalizing identities through `mailmap` when one is given.
+    pub fn from_hunk(
         line_number: usize,
         content: String,
-        commit_hash: String,
-        author: String,
-        author_email: String,
-        author_time: i64,
-        committer: String,
-        commit_time: i64,
+        hunk: &BlameHunk,
+        mailmap: Option<&Mailmap>,
         summary: String,
     ) -> Self {
+        let final_sig = hunk.final_signature();
+        let orig_sig = hunk.orig_signature();
+        let raw_author = final_sig.name().unwrap_or("").to_string();
+        let raw_author_email = final_sig.email().unwrap_or("").to_string();
+        let raw_committer = orig_sig.name().unwrap_or("").to_string();
+
+        let ((author, author_email), committer) = match mailmap {
+            Some(mailmap) => (
+                mailmapped_identity(mailmap, &final_sig),
+                mailmapped_identity(mailmap, &orig_sig).0,
+            ),
+            None => (
+                (raw_author.clone(), raw_author_email.clone()),
+                raw_committer.clone(),
+            ),
+        };
+
         BlameLine {
             line_number,
             content,
-            commit_hash,
+            commit_hash: hunk.final_commit_id().to_string(),
             author,
             author_email,
-            author_time,
+            author_time: final_sig.when().seconds(),
+            author_offset_minutes: final_sig.when().offset_minutes(),
             committer,
-            commit_time,
+            commit_time: orig_sig.when().seconds(),
+            commit_offset_minutes: orig_sig.when().offset_minutes(),
             summary,
+            raw_author,
+            raw_author_email,
+            raw_committer,
+        }
+    }
+}
+
+/// Why a file could not be blamed.
+#[pyclass(module = "rustygit")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BlameErrorKind {
+    /// The path does not exist or could not be resolved.
+    NotFound,
+    /// No Git repository contains the path.
+    NotInRepository,
+    /// The path is outside the repository's working directory (or the repository is bare).
+    OutsideWorkdir,
+    /// libgit2 failed while computing the blame.
+    Git,
+    /// The file could not be read from disk.
+    Io,
+}
+#[pymethods]
+impl BlameErrorKind {
+    fn __hash__(&self) -> isize {
+        *self as isize
+    }
+
+    /// Pickle by variant name so the class attribute is restored on load
+    fn __reduce__(&self, py: Python) -> PyResult<(PyObject, (PyObject, String))> {
+        let getattr = py.import("builtins")?.getattr("getattr")?.into();
+        let cls = py.get_type::<BlameErrorKind>().into();
+        Ok((getattr, (cls, format!("{:?}", self))))
+    }
+}
+
+#[pyclass(module = "rustygit")]
+#[derive(Clone, Debug)]
+pub struct BlameError {
+    #[pyo3(get)]
+    pub path: String,
+    #[pyo3(get)]
+    pub kind: BlameErrorKind,
+    #[pyo3(get)]
+    pub message: String,
+}
+
+py_model!(
+    BlameError,
+    fields: [path, kind, message],
+    key: [path, kind, message],
+    repr: [path, kind, message],
+);
+
+impl BlameError {
+    pub fn new(path: String, kind: BlameErrorKind, message: String) -> Self {
+        BlameError {
+            path,
+            kind,
+            message,
         }
     }
 }
+
+#[pyclass(module = "rustygit")]
+#[derive(Clone, Debug)]
+pub struct BlameResult {
+    #[pyo3(get)]
+    pub results: HashMap<String, Vec<BlameLine>>,
+    #[pyo3(get)]
+    pub errors: Vec<BlameError>,
+}
+py_model!(
+    BlameResult,
+    fields: [results, errors],
+    repr: [results, errors],
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestRepo;
+
+    fn load(t: &TestRepo, id: Oid, mailmap: Option<&Mailmap>) -> Commit {
+        Commit::from_git_commit(&t.git().find_commit(id).unwrap(), mailmap)
+    }
+
+    #[test]
+    fn message_views_parse_subject_body_and_trailers() {
+        let t = TestRepo::new();
+        let id = t.commit(
+            "file.txt",
+            "one\n",
+            "feat(api)!: add export\n\nExports commits as rows.\n\n\
+             Co-authored-by: Ann Other <ann@old.example.com>\n\
+             Reviewed-by: Rev Iewer <rev@example.com>\n",
+        );
+        let commit = load(&t, id, None);
+
+        assert_eq!(commit.subject(), "feat(api)!: add export");
+        assert!(commit
+            .body()
+            .starts_with("Exports commits as rows.\n\nCo-authored-by"));
+        let trailers: Vec<(String, String)> = commit
+            .trailers()
+            .into_iter()
+            .map(|trailer| (trailer.key, trailer.value))
+            .collect();
+        assert_eq!(
+            trailers,
+            vec![
+                (
+                    "Co-authored-by".to_string(),
+                    "Ann Other <ann@old.example.com>".to_string()
+                ),
+                (
+                    "Reviewed-by".to_string(),
+                    "Rev Iewer <rev@example.com>".to_string()
+                ),
+            ]
+        );
+        let conventional = commit.conventional().unwrap();
+        assert_eq!(conventional.r#type, "feat");
+        assert_eq!(conventional.scope.as_deref(), Some("api"));
+        assert!(conventional.breaking);
+        assert_eq!(conventional.description, "add export");
+
+        assert_eq!(commit.co_authors.len(), 1);
+        assert_eq!(commit.co_authors[0].name, "Ann Other");
+        assert_eq!(commit.co_authors[0].email, "ann@old.example.com");
+    }
+
+    #[test]
+    fn co_authors_go_through_the_mailmap() {
+        let t = TestRepo::new();
+        let id = t.commit(
+            "file.txt",
+            "one\n",
+            "Fix a typo\n\nCo-authored-by: Ann <ann@old.example.com>\n",
+        );
+        let mailmap =
+            Mailmap::from_buffer("Ann Other <ann@example.com> <ann@old.example.com>\n").unwrap();
+        let commit = load(&t, id, Some(&mailmap));
+
+        assert_eq!(commit.co_authors[0].name, "Ann Other");
+        assert_eq!(commit.co_authors[0].email, "ann@example.com");
+        assert!(commit.conventional().is_none());
+    }
+
+    #[test]
+    fn parent_commits_use_the_given_mailmap() {
+        let t = TestRepo::new();
+        let first = t.commit("file.txt", "one\n", "first");
+        let second = load(&t, t.commit("file.txt", "two\n", "second"), None);
+        let mailmap = "Canonical <canonical@example.com> <test@example.com>\n";
+
+        Python::with_gil(|py| {
+            let repo = t.py_repo(py);
+            let parents = second.parent_commits(repo.borrow(py), None).unwrap();
+            assert_eq!(parents.len(), 1);
+            assert_eq!(parents[0].hash, first.to_string());
+            assert_eq!(parents[0].author, "Test User");
+
+            let parents = second
+                .parent_commits(repo.borrow(py), Some(mailmap))
+                .unwrap();
+            assert_eq!(parents[0].author, "Canonical");
+            assert_eq!(parents[0].author_email, "canonical@example.com");
+            assert_eq!(parents[0].raw_author, "Test User");
+        });
+    }
+
+    #[test]
+    fn a_subject_only_message_has_no_body_or_trailers() {
+        let t = TestRepo::new();
+        let id = t.commit("file.txt", "one\n", "Signed-off-by: Not A Trailer\n");
+        let commit = load(&t, id, None);
+
+        assert_eq!(commit.subject(), "Signed-off-by: Not A Trailer");
+        assert_eq!(commit.body(), "");
+        assert!(commit.trailers().is_empty());
+        assert!(commit.co_authors.is_empty());
+    }
+}
@@ -0,0 +1,293 @@
+use crate::repo::Repo;
+use crate::utils::git_err_to_py_err;
+use git2::{ConfigLevel, ErrorCode};
+use pyo3::exceptions::{PyKeyError, PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyLong, PyString};
+
+use super::model::{Config, ConfigEntry};
+
+fn parse_level(level: &str) -> PyResult<ConfigLevel> {
+    match level {
+        "programdata" => Ok(ConfigLevel::ProgramData),
+        "system" => Ok(ConfigLevel::System),
+        "xdg" => Ok(ConfigLevel::XDG),
+        "global" => Ok(ConfigLevel::Global),
+        other => Err(PyValueError::new_err(format!(
+            "Unknown config level '{}', expected 'default', 'global', 'xdg', 'system' or 'programdata'",
+            other
+        ))),
+    }
+}
+
+fn level_name(level: ConfigLevel) -> &'static str {
+    match level {
+        ConfigLevel::ProgramData => "programdata",
+        ConfigLevel::System => "system",
+        ConfigLevel::XDG => "xdg",
+        ConfigLevel::Global => "global",
+        ConfigLevel::Local => "local",
+        ConfigLevel::Worktree => "worktree",
+        ConfigLevel::App => "app",
+        ConfigLevel::Highest => "highest",
+    }
+}
+
+/// The value of a lookup, or `None` when the variable isn't set.
+fn optional<T>(value: Result<T, git2::Error>) -> PyResult<Option<T>> {
+    match value {
+        Ok(value) => Ok(Some(value)),
+        Err(e) if e.code() == ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(git_err_to_py_err(e)),
+    }
+}
+
+/// Translate a glob over variable names into the anchored regular
+/// expression libgit2 filters entries with.
+///
+/// Entries come back with their section and key lower-cased, so those parts
+/// of the glob are too; a subsection between them keeps its case.
+fn glob_to_regex(glob: &str) -> String {
+    let glob = match (glob.find('.'), glob.rfind('.')) {
+        (Some(first), Some(last)) => format!(
+            "{}{}{}",
+            glob[..first].to_lowercase(),
+            &glob[first..last],
+            glob[last..].to_lowercase()
+        ),
+        _ => glob.to_lowercase(),
+    };
+    let mut regex = String::with_capacity(glob.len() + 2);
+    regex.push('^');
+    for c in glob.chars() {
+        match c {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            '.' | '^' | '$' | '+' | '(' | ')' | '[' | ']' | '{' | '}' | '|' | '\\' => {
+                regex.push('\\');
+                regex.push(c);
+            }
+            c => regex.push(c),
+        }
+    }
+    regex.push('$');
+    regex
+}
+
+fn collect_entries(mut entries: git2::ConfigEntries) -> Result<Vec<ConfigEntry>, git2::Error> {
+    let mut collected = Vec::new();
+    while let Some(entry) = entries.next() {
+        let entry = entry?;
+        collected.push(ConfigEntry {
+            name: entry.name().unwrap_or_default().to_string(),
+            value: entry.value().map(str::to_string),
+            level: level_name(entry.level()).to_string(),
+        });
+    }
+    Ok(collected)
+}
+
+#[pymethods]
+impl Config {
+    /// Open the configuration outside any repository: `default` layers the
+    /// system, XDG and global files; the other levels open just that file.
+    #[staticmethod]
+    #[pyo3(signature = (level="default"))]
+    fn open(level: &str) -> PyResult<Config> {
+        let config = git2::Config::open_default().map_err(git_err_to_py_err)?;
+        let inner = match level {
+            "default" => config,
+            level => config
+                .open_level(parse_level(level)?)
+                .map_err(git_err_to_py_err)?,
+        };
+        Ok(Config { inner })
+    }
+
+    #[pyo3(signature = (name, default=None))]
+    fn get_string(&self, name: &str, default: Option<String>) -> PyResult<Option<String>> {
+        Ok(optional(self.inner.get_string(name))?.or(default))
+    }
+
+    /// Read a boolean, accepting git's spellings (`yes`, `on`, `1`, ...)
+    #[pyo3(signature = (name, default=None))]
+    fn get_bool(&self, name: &str, default: Option<bool>) -> PyResult<Option<bool>> {
+        Ok(optional(self.inner.get_bool(name))?.or(default))
+    }
+
+    /// Read an integer, accepting the `k`, `m` and `g` suffixes
+    #[pyo3(signature = (name, default=None))]
+    fn get_int(&self, name: &str, default: Option<i64>) -> PyResult<Option<i64>> {
+        Ok(optional(self.inner.get_i64(name))?.or(default))
+    }
+
+    /// Read a path, expanding a leading `~/`
+    #[pyo3(signature = (name, default=None))]
+    fn get_path(&self, name: &str, default: Option<String>) -> PyResult<Option<String>> {
+        let path = optional(self.inner.get_path(name))?;
+        Ok(path
+            .map(|path| path.to_string_lossy().into_owned())
+            .or(default))
+    }
+
+    /// Every value of a variable that may be set more than once, such as
+    /// `remote.origin.fetch`, optionally only those matching `regexp`
+    #[pyo3(signature = (name, regexp=None))]
+    fn get_multivar(&self, name: &str, regexp: Option<&str>) -> PyResult<Vec<String>> {
+        let entries = match optional(self.inner.multivar(name, regexp))? {
+            Some(entries) => collect_entries(entries).map_err(git_err_to_py_err)?,
+            None => Vec::new(),
+        };
+        Ok(entries
+            .into_iter()
+            .filter_map(|entry| entry.value)
+            .collect())
+    }
+
+    /// Set a variable to a string, bool or int
+    fn set(&mut self, name: &str, value: &PyAny) -> PyResult<()> {
+        if let Ok(value) = value.downcast::<PyBool>() {
+            self.inner.set_bool(name, value.is_true())
+        } else if value.is_instance_of::<PyLong>() {
+            self.inner.set_i64(name, value.extract()?)
+        } else if let Ok(value) = value.downcast::<PyString>() {
+            self.inner.set_str(name, value.to_str()?)
+        } else {
+            return Err(PyTypeError::new_err(format!(
+                "Config values must be str, bool or int, not {}",
+                value.get_type().name()?
+            )));
+        }
+        .map_err(git_err_to_py_err)
+    }
+
+    /// Remove a variable from the highest level loaded
+    fn unset(&mut self, name: &str) -> PyResult<()> {
+        match self.inner.remove(name) {
+            Ok(()) => Ok(()),
+            Err(e) if e.code() == ErrorCode::NotFound => Err(PyKeyError::new_err(name.to_string())),
+            Err(e) => Err(git_err_to_py_err(e)),
+        }
+    }
+
+    /// Every variable, or those whose whole name matches `glob`, where `*`
+    /// matches any run of characters and `?` any one, e.g. `user.*` or
+    /// `remote.*.url`. Only subsection names are matched case-sensitively.
+    #[pyo3(signature = (glob=None))]
+    fn entries(&self, glob: Option<&str>) -> PyResult<Vec<ConfigEntry>> {
+        let regexp = glob.map(glob_to_regex);
+        let entries = self
+            .inner
+            .entries(regexp.as_deref())
+            .map_err(git_err_to_py_err)?;
+        collect_entries(entries).map_err(git_err_to_py_err)
+    }
+
+    /// A read-only copy that later changes to the files don't affect
+    fn snapshot(&mut self) -> PyResult<Config> {
+        let inner = self.inner.snapshot().map_err(git_err_to_py_err)?;
+        Ok(Config { inner })
+    }
+}
+
+#[pymethods]
+impl Repo {
+    /// The repository's configuration, layered over the global levels.
+    /// Writes go to `.git/config`.
+    fn config(&self) -> PyResult<Config> {
+        let inner = self.inner.config().map_err(git_err_to_py_err)?;
+        Ok(Config { inner })
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestRepo;
+
+    fn local_names(config: &Config, glob: Option<&str>) -> Vec<String> {
+        let mut names: Vec<String> = config
+            .entries(glob)
+            .unwrap()
+            .into_iter()
+            .filter(|entry| entry.level == "local")
+            .map(|entry| entry.name)
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn glob_to_regex_cases() {
+        let cases = [
+            ("user.*", "^user\\..*$"),
+            ("User.Name", "^user\\.name$"),
+            ("REMOTE.Origin.URL", "^remote\\.Origin\\.url$"),
+            ("remote.*.url", "^remote\\..*\\.url$"),
+            ("Core", "^core$"),
+            ("a?b+c", "^a.b\\+c$"),
+        ];
+        for (glob, regex) in cases {
+            assert_eq!(glob_to_regex(glob), regex, "{:?}", glob);
+        }
+    }
+
+    #[test]
+    fn entries_match_globs_case_insensitively_outside_subsections() {
+        let t = TestRepo::new();
+        let mut config = t.repo.config().unwrap();
+        Python::with_gil(|py| {
+            let url = PyString::new(py, "https://example.com/repo.git");
+            config.set("remote.Origin.url", url).unwrap();
+        });
+
+        assert_eq!(
+            local_names(&config, Some("USER.*")),
+            vec!["user.email", "user.name"]
+        );
+        assert_eq!(local_names(&config, Some("user.nam?")), vec!["user.name"]);
+        assert_eq!(
+            local_names(&config, Some("Remote.*.URL")),
+            vec!["remote.Origin.url"]
+        );
+        assert_eq!(
+            local_names(&config, Some("remote.Origin.Url")),
+            vec!["remote.Origin.url"]
+        );
+        assert!(local_names(&config, Some("remote.origin.url")).is_empty());
+        assert!(local_names(&config, Some("user")).is_empty());
+    }
+
+    #[test]
+    fn typed_getters_set_and_unset() {
+        let t = TestRepo::new();
+        let mut config = t.repo.config().unwrap();
+        Python::with_gil(|py| {
+            config.set("rustygit.flag", PyBool::new(py, true)).unwrap();
+            config
+                .set("rustygit.count", 3i64.into_py(py).as_ref(py))
+                .unwrap();
+            let err = config.set("rustygit.bad", 1.5f64.into_py(py).as_ref(py));
+            assert!(err.unwrap_err().is_instance_of::<PyTypeError>(py));
+        });
+
+        assert_eq!(config.get_bool("rustygit.flag", None).unwrap(), Some(true));
+        assert_eq!(config.get_int("rustygit.count", None).unwrap(), Some(3));
+        assert_eq!(
+            config.get_string("user.name", None).unwrap().as_deref(),
+            Some("Test User")
+        );
+        assert_eq!(
+            config.get_int("rustygit.missing", Some(7)).unwrap(),
+            Some(7)
+        );
+
+        let snapshot = config.snapshot().unwrap();
+        config.unset("rustygit.count").unwrap();
+        assert_eq!(config.get_int("rustygit.count", None).unwrap(), None);
+        assert_eq!(snapshot.get_int("rustygit.count", None).unwrap(), Some(3));
+        Python::with_gil(|py| {
+            let err = config.unset("rustygit.count").unwrap_err();
+            assert!(err.is_instance_of::<PyKeyError>(py));
+        });
+    }
+}
@@ -0,0 +1,32 @@
+use crate::utils::py_model;
+use pyo3::prelude::*;
+
+/// A configuration variable and the level of the file that set it.
+#[pyclass(module = "rustygit")]
+#[derive(Clone, Debug)]
+pub struct ConfigEntry {
+    #[pyo3(get)]
+    pub name: String,
+    /// `None` for a bare boolean such as `[core] bare`
+    #[pyo3(get)]
+    pub value: Option<String>,
+    /// `system`, `xdg`, `global`, `local`, `worktree`, `app` or
+    /// `programdata`
+    #[pyo3(get)]
+    pub level: String,
+}
+
+py_model!(
+    ConfigEntry,
+    fields: [name, value, level],
+    key: [name, value, level],
+    repr: [name, value, level],
+);
+
+/// A git configuration: a repository's (`Repo.config`) with every level
+/// layered, or the global levels (`Config.open`). Values are read from the
+/// highest level that sets them and written to the highest level loaded.
+#[pyclass(unsendable, module = "rustygit")]
+pub struct Config {
+    pub(crate) inner: git2::Config,
+}
@@ -0,0 +1,301 @@
+use crate::utils::{git_err_to_py_err, load_mailmap, mailmapped_identity, walk_revisions};
+use git2::{DiffOptions, Mailmap, Oid, Repository};
+use pyo3::prelude::*;
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use super::model::Contributor;
+
+/// What a single commit contributes to its author's totals.
+struct CommitStats {
+    name: String,
+    email: String,
+    time: i64,
+    offset_minutes: i32,
+    insertions: usize,
+    deletions: usize,
+    files: Vec<String>,
+}
+
+/// Per-identity commit counts and line statistics, like `git shortlog -sne`
+/// combined with `git log --shortstat`.
+///
+/// `range` is either a single revision or an `A..B` range and defaults to
+/// `HEAD`. `since` and `until` bound the author time (Unix seconds,
+/// inclusive), the same time the first/last commit and active-day stats
+/// use. When `paths` is given only non-merge commits touching those
+/// pathspecs are counted, and only changes under them contribute to the line
+/// and file statistics. Identities are merged through the supplied `mailmap`
+/// text, or the repository's own mailmap when none is given.
+pub fn compute_contributors(
+    git_dir: &Path,
+    range: Option<&str>,
+    paths: &[String],
+    since: Option<i64>,
+    until: Option<i64>,
+    mailmap: Option<&str>,
+) -> PyResult<Vec<Contributor>> {
+    let repo = Repository::open(git_dir).map_err(git_err_to_py_err)?;
+    let oids = walk_revisions(&repo, range).map_err(git_err_to_py_err)?;
+
+    let per_commit = oids
+        .par_iter()
+        .map_init(
+            || {
+                let repo = Repository::open(git_dir)?;
+                let mailmap = load_mailmap(&repo, mailmap)?;
+                Ok::<_, git2::Error>((repo, mailmap))
+            },
+            |state, oid| match state {
+                Ok((repo, mailmap)) => commit_stats(repo, mailmap, *oid, paths, since, until),
+                Err(e) => Err(git2::Error::new(e.code(), e.class(), e.message())),
+            },
+        )
+        .collect::<Result<Vec<_>, git2::Error>>()
+        .map_err(git_err_to_py_err)?;
+
+    Ok(aggregate(per_commit.into_iter().flatten()))
+}
+
+fn commit_stats(
+    repo: &Repository,
+    mailmap: &Mailmap,
+    oid: Oid,
+    paths: &[String],
+    since: Option<i64>,
+    until: Option<i64>,
+) -> Result<Option<CommitStats>, git2::Error> {
+    let commit = repo.find_commit(oid)?;
+    let author_time = commit.author().when().seconds();
+    if since.is_some_and(|s| author_time < s) || until.is_some_and(|u| author_time > u) {
+        return Ok(None);
+    }
+
+    let author = commit.author();
+    let (name, email) = mailmapped_identity(mailmap, &author);
+    let mut stats = CommitStats {
+        name,
+        email,
+        time: author.when().seconds(),
+        offset_minutes: author.when().offset_minutes(),
+        insertions: 0,
+        deletions: 0,
+        files: Vec::new(),
+    };
+
+    // Merges are counted but, as with `git log --shortstat`, carry no line stats.
+    if commit.parent_count() > 1 {
+        return Ok(if paths.is_empty() { Some(stats) } else { None });
+    }
+
+    let parent_tree = match commit.parent(0) {
+        Ok(parent) => Some(parent.tree()?),
+        Err(_) => None,
+    };
+    let tree = commit.tree()?;
+
+    let mut options = DiffOptions::new();
+    for path in paths {
+        options.pathspec(path);
+    }
+    let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut options))?;
+    if !paths.is_empty() && diff.deltas().len() == 0 {
+        return Ok(None);
+    }
+
+    let diff_stats = diff.stats()?;
+    stats.insertions = diff_stats.insertions();
+    stats.deletions = diff_stats.deletions();
+    stats.files = diff
+        .deltas()
+        .filter_map(|delta| {
+            delta
+                .new_file()
+                .path()
+                .or_else(|| delta.old_file().path())
+                .and_then(|p| p.to_str())
+                .map(String::from)
+        })
+        .collect();
+
+    Ok(Some(stats))
+}
+
+fn aggregate(per_commit: impl Iterator<Item = CommitStats>) -> Vec<Contributor> {
+    struct Totals {
+        contributor: Contributor,
+        days: HashSet<i64>,
+        files: HashSet<String>,
+    }
+
+    let mut by_identity: HashMap<(String, String), Totals> = HashMap::new();
+    for stats in per_commit {
+        let totals = by_identity
+            .entry((stats.name.clone(), stats.email.clone()))
+            .or_insert_with(|| Totals {
+                contributor: Contributor {
+                    name: stats.name.clone(),
+                    email: stats.email.clone(),
+                    commits: 0,
+                    insertions: 0,
+                    deletions: 0,
+                    first_commit_time: stats.time,
+                    last_commit_time: stats.time,
+                    active_days: 0,
+                    files_touched: 0,
+                },
+                days: HashSet::new(),
+                files: HashSet::new(),
+            });
+
+        let c = &mut totals.contributor;
+        c.commits += 1;
+        c.insertions += stats.insertions;
+        c.deletions += stats.deletions;
+        c.first_commit_time = c.first_commit_time.min(stats.time);
+        c.last_commit_time = c.last_commit_time.max(stats.time);
+
+        // Days are counted in the author's own timezone.
+        let local = stats.time + i64::from(stats.offset_minutes) * 60;
+        totals.days.insert(local.div_euclid(86_400));
+        totals.files.extend(stats.files);
+    }
+
+    let mut contributors: Vec<Contributor> = by_identity
+        .into_values()
+        .map(|mut totals| {
+            totals.contributor.active_days = totals.days.len();
+            totals.contributor.files_touched = totals.files.len();
+            totals.contributor
+        })
+        .collect();
+
+    contributors.sort_by(|a, b| {
+        b.commits
+            .cmp(&a.commits)
+            .then_with(|| a.name.cmp(&b.name))
+            .then_with(|| a.email.cmp(&b.email))
+    });
+    contributors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestRepo;
+    use git2::{Signature, Time};
+
+    const DAY: i64 = 86_400;
+    const START: i64 = 1_700_000_000 - 1_700_000_000 % DAY + 3_600;
+
+    /// Commit `content` to `path` as `name <email>` at `time`.
+    fn commit_as(t: &TestRepo, path: &str, content: &str, name: &str, email: &str, time: i64) {
+        std::fs::create_dir_all(t.path().join(path).parent().unwrap()).unwrap();
+        t.write(path, content);
+        let repo = t.git();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(path)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::new(name, email, &Time::new(time, 0)).unwrap();
+        let parent = repo.head().ok().map(|head| head.peel_to_commit().unwrap());
+        let parents: Vec<_> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &signature, &signature, path, &tree, &parents)
+            .unwrap();
+    }
+
+    fn history() -> TestRepo {
+        let t = TestRepo::new();
+        commit_as(
+            &t,
+            "a.txt",
+            "one\ntwo\n",
+            "Alice",
+            "alice@old.example.com",
+            START,
+        );
+        commit_as(
+            &t,
+            "a.txt",
+            "one\n2\n",
+            "Alice B.",
+            "alice@example.com",
+            START + 3_600,
+        );
+        commit_as(
+            &t,
+            "b/b.txt",
+            "b\n",
+            "Bob",
+            "bob@example.com",
+            START + 2 * DAY,
+        );
+        t
+    }
+
+    const MAILMAP: &str = "Alice B. <alice@example.com> <alice@old.example.com>\n";
+
+    fn summary(contributors: &[Contributor]) -> Vec<(&str, usize, usize, usize)> {
+        contributors
+            .iter()
+            .map(|c| (c.name.as_str(), c.commits, c.insertions, c.deletions))
+            .collect()
+    }
+
+    #[test]
+    fn contributors_merge_identities_through_the_mailmap() {
+        let t = history();
+        let contributors =
+            compute_contributors(t.git().path(), None, &[], None, None, Some(MAILMAP)).unwrap();
+
+        assert_eq!(
+            summary(&contributors),
+            vec![("Alice B.", 2, 3, 1), ("Bob", 1, 1, 0)]
+        );
+        let alice = &contributors[0];
+        assert_eq!(alice.email, "alice@example.com");
+        assert_eq!(alice.first_commit_time, START);
+        assert_eq!(alice.last_commit_time, START + 3_600);
+        assert_eq!(alice.active_days, 1);
+        assert_eq!(alice.files_touched, 1);
+
+        let unmapped =
+            compute_contributors(t.git().path(), None, &[], None, None, Some("")).unwrap();
+        assert_eq!(unmapped.len(), 3);
+    }
+
+    #[test]
+    fn contributors_filter_on_paths_and_author_time() {
+        let t = history();
+        let git_dir = t.git().path();
+
+        let under_b = ["b".to_string()];
+        let contributors =
+            compute_contributors(git_dir, None, &under_b, None, None, Some(MAILMAP)).unwrap();
+        assert_eq!(summary(&contributors), vec![("Bob", 1, 1, 0)]);
+        assert_eq!(contributors[0].files_touched, 1);
+
+        let since =
+            compute_contributors(git_dir, None, &[], Some(START + 1), None, Some(MAILMAP)).unwrap();
+        assert_eq!(
+            summary(&since),
+            vec![("Alice B.", 1, 1, 1), ("Bob", 1, 1, 0)]
+        );
+
+        let until =
+            compute_contributors(git_dir, None, &[], None, Some(START), Some(MAILMAP)).unwrap();
+        assert_eq!(summary(&until), vec![("Alice B.", 1, 2, 0)]);
+
+        let range = compute_contributors(
+            git_dir,
+            Some("HEAD~1..HEAD"),
+            &[],
+            None,
+            None,
+            Some(MAILMAP),
+        )
+        .unwrap();
+        assert_eq!(summary(&range), vec![("Bob", 1, 1, 0)]);
+    }
+}
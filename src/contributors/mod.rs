@@ -0,0 +1,6 @@
+pub mod logic;
+pub mod model;
+
+pub use logic::compute_contributors;
+
+pub use model::Contributor;
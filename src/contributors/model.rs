@@ -0,0 +1,35 @@
+use crate::utils::py_model;
+use pyo3::prelude::*;
+
+#[pyclass(module = "rustygit")]
+#[derive(Clone, Debug)]
+pub struct Contributor {
+    #[pyo3(get)]
+    pub name: String,
+    #[pyo3(get)]
+    pub email: String,
+    #[pyo3(get)]
+    pub commits: usize,
+    #[pyo3(get)]
+    pub insertions: usize,
+    #[pyo3(get)]
+    pub deletions: usize,
+    #[pyo3(get)]
+    pub first_commit_time: i64,
+    #[pyo3(get)]
+    pub last_commit_time: i64,
+    #[pyo3(get)]
+    pub active_days: usize,
+    #[pyo3(get)]
+    pub files_touched: usize,
+}
+
+py_model!(
+    Contributor,
+    fields: [
+        name, email, commits, insertions, deletions, first_commit_time, last_commit_time,
+        active_days, files_touched
+    ],
+    key: [name, email, commits],
+    repr: [name, email, commits],
+);
@@ -0,0 +1,484 @@
+//! Columnar export of history, per-file diff stats and blame.
+//!
+//! Column names and types are part of the public API; see the schema
+//! functions below and `python/rustygit/export.pyi`. New columns may be
+//! appended, existing ones are not renamed or retyped.
+
+use crate::commits::logic::blame_files;
+use crate::commits::message::split_message;
+use crate::commits::{BlameLine, Commit};
+use crate::utils::{git_err_to_py_err, load_mailmap, walk_revisions};
+use arrow::array::{
+    ArrayRef, BooleanArray, Int32Array, ListBuilder, RecordBatch, StringArray, StringBuilder,
+    TimestampSecondArray, UInt32Array, UInt64Array,
+};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use git2::{Delta, DiffFindOptions, Mailmap, Oid, Patch, Repository};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use rayon::prelude::*;
+use std::path::Path;
+use std::sync::Arc;
+
+use super::model::ArrowTable;
+
+/// Rows per record batch, so that large histories stream in pieces.
+const BATCH_ROWS: usize = 65_536;
+
+fn utc_seconds() -> DataType {
+    DataType::Timestamp(TimeUnit::Second, Some("UTC".into()))
+}
+
+/// Schema of `export_commits`: one row per commit.
+pub fn commits_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("hash", DataType::Utf8, false),
+        Field::new("tree_id", DataType::Utf8, false),
+        Field::new("author", DataType::Utf8, false),
+        Field::new("author_email", DataType::Utf8, false),
+        Field::new("author_time", utc_seconds(), false),
+        Field::new("author_offset_minutes", DataType::Int32, false),
+        Field::new("committer", DataType::Utf8, false),
+        Field::new("committer_email", DataType::Utf8, false),
+        Field::new("commit_time", utc_seconds(), false),
+        Field::new("commit_offset_minutes", DataType::Int32, false),
+        Field::new("subject", DataType::Utf8, false),
+        Field::new("message", DataType::Utf8, false),
+        Field::new(
+            "parents",
+            DataType::List(Arc::new(Field::new("item", DataType::Utf8, true))),
+            false,
+        ),
+        Field::new("is_signed", DataType::Boolean, false),
+    ]))
+}
+
+/// Schema of `export_file_stats`: one row per file changed by a commit.
+pub fn file_stats_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("commit_hash", DataType::Utf8, false),
+        Field::new("commit_time", utc_seconds(), false),
+        Field::new("path", DataType::Utf8, false),
+        Field::new("old_path", DataType::Utf8, true),
+        Field::new("status", DataType::Utf8, false),
+        Field::new("additions", DataType::UInt64, false),
+        Field::new("deletions", DataType::UInt64, false),
+        Field::new("is_binary", DataType::Boolean, false),
+    ]))
+}
+
+/// Schema of `export_blame`: one row per line of each blamed file.
+pub fn blame_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("path", DataType::Utf8, false),
+        Field::new("line_number", DataType::UInt32, false),
+        Field::new("content", DataType::Utf8, false),
+        Field::new("commit_hash", DataType::Utf8, false),
+        Field::new("author", DataType::Utf8, false),
+        Field::new("author_email", DataType::Utf8, false),
+        Field::new("author_time", utc_seconds(), false),
+        Field::new("committer", DataType::Utf8, false),
+        Field::new("commit_time", utc_seconds(), false),
+        Field::new("summary", DataType::Utf8, false),
+    ]))
+}
+
+fn utc_timestamps(values: impl Iterator<Item = i64>) -> ArrayRef {
+    Arc::new(TimestampSecondArray::from_iter_values(values).with_timezone("UTC"))
+}
+
+fn strings<'a>(values: impl Iterator<Item = &'a str>) -> ArrayRef {
+    Arc::new(StringArray::from_iter_values(values))
+}
+
+fn table(schema: SchemaRef, batches: Vec<RecordBatch>) -> ArrowTable {
+    ArrowTable {
+        schema,
+        batches,
+        errors: Vec::new(),
+    }
+}
+
+fn arrow_err(err: arrow::error::ArrowError) -> PyErr {
+    PyValueError::new_err(format!("Arrow error: {}", err))
+}
+
+/// Export the commit history as an Arrow table.
+#[pyfunction]
+#[pyo3(signature = (path, rev=None, mailmap=None))]
+pub fn export_commits(
+    py: Python,
+    path: &str,
+    rev: Option<&str>,
+    mailmap: Option<&str>,
+) -> PyResult<ArrowTable> {
+    py.allow_threads(|| {
+        let repo = Repository::open(path).map_err(git_err_to_py_err)?;
+        let git_dir = repo.path().to_path_buf();
+        let oids = walk_revisions(&repo, rev).map_err(git_err_to_py_err)?;
+
+        let commits = oids
+            .par_iter()
+            .map_init(
+                || {
+                    let repo = Repository::open(&git_dir)?;
+                    let mailmap = load_mailmap(&repo, mailmap)?;
+                    Ok::<_, git2::Error>((repo, mailmap))
+                },
+                |state, oid| match state {
+                    Ok((repo, mailmap)) => repo
+                        .find_commit(*oid)
+                        .map(|commit| Commit::from_git_commit(&commit, Some(mailmap))),
+                    Err(e) => Err(git2::Error::new(e.code(), e.class(), e.message())),
+                },
+            )
+            .collect::<Result<Vec<_>, git2::Error>>()
+            .map_err(git_err_to_py_err)?;
+
+        let schema = commits_schema();
+        let batches = commits
+            .chunks(BATCH_ROWS)
+            .map(|chunk| commits_batch(&schema, chunk))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(arrow_err)?;
+        Ok(table(schema, batches))
+    })
+}
+
+fn commits_batch(
+    schema: &SchemaRef,
+    commits: &[Commit],
+) -> Result<RecordBatch, arrow::error::ArrowError> {
+    let mut parents = ListBuilder::new(StringBuilder::new());
+    for commit in commits {
+        for parent in &commit.parents {
+            parents.values().append_value(parent);
+        }
+        parents.append(true);
+    }
+    let subjects: Vec<String> = commits
+        .iter()
+        .map(|c| split_message(&c.message).0)
+        .collect();
+
+    RecordBatch::try_new(
+        schema.clone(),
+        vec![
+            strings(commits.iter().map(|c| c.hash.as_str())),
+            strings(commits.iter().map(|c| c.tree_id.as_str())),
+            strings(commits.iter().map(|c| c.author.as_str())),
+            strings(commits.iter().map(|c| c.author_email.as_str())),
+            utc_timestamps(commits.iter().map(|c| c.author_time)),
+            Arc::new(Int32Array::from_iter_values(
+                commits.iter().map(|c| c.author_offset_minutes),
+            )),
+            strings(commits.iter().map(|c| c.committer.as_str())),
+            strings(commits.iter().map(|c| c.committer_email.as_str())),
+            utc_timestamps(commits.iter().map(|c| c.commit_time)),
+            Arc::new(Int32Array::from_iter_values(
+                commits.iter().map(|c| c.commit_offset_minutes),
+            )),
+            strings(subjects.iter().map(String::as_str)),
+            strings(commits.iter().map(|c| c.message.as_str())),
+            Arc::new(parents.finish()),
+            Arc::new(BooleanArray::from_iter(
+                commits.iter().map(|c| Some(c.is_signed)),
+            )),
+        ],
+    )
+}
+
+/// A file changed by a commit, relative to its first parent.
+struct FileStat {
+    commit_hash: String,
+    commit_time: i64,
+    path: String,
+    old_path: Option<String>,
+    status: &'static str,
+    additions: usize,
+    deletions: usize,
+    is_binary: bool,
+}
+
+/// Export per-file additions and deletions for every non-merge commit.
+#[pyfunction]
+#[pyo3(signature = (path, rev=None))]
+pub fn export_file_stats(py: Python, path: &str, rev: Option<&str>) -> PyResult<ArrowTable> {
+    py.allow_threads(|| {
+        let repo = Repository::open(path).map_err(git_err_to_py_err)?;
+        let git_dir = repo.path().to_path_buf();
+        let oids = walk_revisions(&repo, rev).map_err(git_err_to_py_err)?;
+
+        let per_commit = oids
+            .par_iter()
+            .map_init(
+                || Repository::open(&git_dir),
+                |repo, oid| match repo {
+                    Ok(repo) => commit_file_stats(repo, *oid),
+                    Err(e) => Err(git2::Error::new(e.code(), e.class(), e.message())),
+                },
+            )
+            .collect::<Result<Vec<_>, git2::Error>>()
+            .map_err(git_err_to_py_err)?;
+        let stats: Vec<FileStat> = per_commit.into_iter().flatten().collect();
+
+        let schema = file_stats_schema();
+        let batches = stats
+            .chunks(BATCH_ROWS)
+            .map(|chunk| file_stats_batch(&schema, chunk))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(arrow_err)?;
+        Ok(table(schema, batches))
+    })
+}
+
+fn commit_file_stats(repo: &Repository, oid: Oid) -> Result<Vec<FileStat>, git2::Error> {
+    let commit = repo.find_commit(oid)?;
+    if commit.parent_count() > 1 {
+        return Ok(Vec::new());
+    }
+    let parent_tree = match commit.parent(0) {
+        Ok(parent) => Some(parent.tree()?),
+        Err(_) => None,
+    };
+    let tree = commit.tree()?;
+    let mut diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
+    diff.find_similar(Some(DiffFindOptions::new().renames(true)))?;
+
+    let hash = commit.id().to_string();
+    let mut stats = Vec::with_capacity(diff.deltas().len());
+    for (idx, delta) in diff.deltas().enumerate() {
+        let new_path = path_string(delta.new_file().path());
+        let old_path = path_string(delta.old_file().path());
+        let status = match delta.status() {
+            Delta::Added => "added",
+            Delta::Deleted => "deleted",
+            Delta::Renamed => "renamed",
+            Delta::Copied => "copied",
+            Delta::Typechange => "typechange",
+            _ => "modified",
+        };
+        let (additions, deletions, is_binary) = match Patch::from_diff(&diff, idx)? {
+            Some(patch) => {
+                let (_, additions, deletions) = patch.line_stats()?;
+                (additions, deletions, delta.flags().is_binary())
+            }
+            None => (0, 0, true),
+        };
+        stats.push(FileStat {
+            commit_hash: hash.clone(),
+            commit_time: commit.time().seconds(),
+            path: new_path
+                .clone()
+                .or_else(|| old_path.clone())
+                .unwrap_or_default(),
+            old_path: match delta.status() {
+                Delta::Renamed | Delta::Copied => old_path,
+                _ => None,
+            },
+            status,
+            additions,
+            deletions,
+            is_binary,
+        });
+    }
+    Ok(stats)
+}
+
+fn path_string(path: Option<&Path>) -> Option<String> {
+    path.map(|p| p.to_string_lossy().into_owned())
+}
+
+fn file_stats_batch(
+    schema: &SchemaRef,
+    stats: &[FileStat],
+) -> Result<RecordBatch, arrow::error::ArrowError> {
+    RecordBatch::try_new(
+        schema.clone(),
+        vec![
+            strings(stats.iter().map(|s| s.commit_hash.as_str())),
+            utc_timestamps(stats.iter().map(|s| s.commit_time)),
+            strings(stats.iter().map(|s| s.path.as_str())),
+            Arc::new(StringArray::from_iter(
+                stats.iter().map(|s| s.old_path.as_deref()),
+            )),
+            strings(stats.iter().map(|s| s.status)),
+            Arc::new(UInt64Array::from_iter_values(
+                stats.iter().map(|s| s.additions as u64),
+            )),
+            Arc::new(UInt64Array::from_iter_values(
+                stats.iter().map(|s| s.deletions as u64),
+            )),
+            Arc::new(BooleanArray::from_iter(
+                stats.iter().map(|s| Some(s.is_binary)),
+            )),
+        ],
+    )
+}
+
+/// Export line-level blame for many files as a single Arrow table.
+///
+/// Files that cannot be blamed are skipped and reported in the table's
+/// `errors`, as `get_blame_for_files` does.
+#[pyfunction]
+#[pyo3(signature = (file_paths, mailmap=None))]
+pub fn export_blame(
+    py: Python,
+    file_paths: Vec<String>,
+    mailmap: Option<&str>,
+) -> PyResult<ArrowTable> {
+    if let Some(text) = mailmap {
+        Mailmap::from_buffer(text).map_err(git_err_to_py_err)?;
+    }
+    py.allow_threads(|| {
+        let mut blame = blame_files(&file_paths, mailmap);
+
+        // Rows follow the order the files were given in.
+        let rows: Vec<(&str, BlameLine)> = file_paths
+            .iter()
+            .filter_map(|p| blame.results.remove(p).map(|lines| (p.as_str(), lines)))
+            .flat_map(|(path, lines)| lines.into_iter().map(move |line| (path, line)))
+            .collect();
+
+        let schema = blame_schema();
+        let batches = rows
+            .chunks(BATCH_ROWS)
+            .map(|chunk| blame_batch(&schema, chunk))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(arrow_err)?;
+        Ok(ArrowTable {
+            errors: blame.errors,
+            ..table(schema, batches)
+        })
+    })
+}
+
+fn blame_batch(
+    schema: &SchemaRef,
+    rows: &[(&str, BlameLine)],
+) -> Result<RecordBatch, arrow::error::ArrowError> {
+    RecordBatch::try_new(
+        schema.clone(),
+        vec![
+            strings(rows.iter().map(|(path, _)| *path)),
+            Arc::new(UInt32Array::from_iter_values(
+                rows.iter().map(|(_, l)| l.line_number as u32),
+            )),
+            strings(rows.iter().map(|(_, l)| l.content.as_str())),
+            strings(rows.iter().map(|(_, l)| l.commit_hash.as_str())),
+            strings(rows.iter().map(|(_, l)| l.author.as_str())),
+            strings(rows.iter().map(|(_, l)| l.author_email.as_str())),
+            utc_timestamps(rows.iter().map(|(_, l)| l.author_time)),
+            strings(rows.iter().map(|(_, l)| l.committer.as_str())),
+            utc_timestamps(rows.iter().map(|(_, l)| l.commit_time)),
+            strings(rows.iter().map(|(_, l)| l.summary.as_str())),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestRepo;
+    use arrow::array::Array;
+
+    fn column<'a, T: 'static>(table: &'a ArrowTable, name: &str) -> &'a T {
+        let idx = table.schema.index_of(name).unwrap();
+        table.batches[0]
+            .column(idx)
+            .as_any()
+            .downcast_ref::<T>()
+            .unwrap()
+    }
+
+    #[test]
+    fn export_commits_has_one_row_per_commit() {
+        let t = TestRepo::new();
+        let first = t.commit("a.txt", "one\n", "first\n\nbody");
+        let second = t.commit("a.txt", "two\n", "second");
+        let mailmap = "Canonical <canonical@example.com> <test@example.com>\n";
+
+        let table = Python::with_gil(|py| {
+            export_commits(py, t.path().to_str().unwrap(), None, Some(mailmap)).unwrap()
+        });
+        assert_eq!(table.schema, commits_schema());
+        assert_eq!(table.batches.len(), 1);
+        assert_eq!(table.batches[0].num_rows(), 2);
+
+        let mut rows: Vec<(String, String, String)> = (0..2)
+            .map(|i| {
+                (
+                    column::<StringArray>(&table, "hash").value(i).to_string(),
+                    column::<StringArray>(&table, "subject")
+                        .value(i)
+                        .to_string(),
+                    column::<StringArray>(&table, "author").value(i).to_string(),
+                )
+            })
+            .collect();
+        rows.sort_by(|a, b| a.1.cmp(&b.1));
+        assert_eq!(
+            rows,
+            vec![
+                (first.to_string(), "first".into(), "Canonical".into()),
+                (second.to_string(), "second".into(), "Canonical".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn export_file_stats_reports_status_and_line_counts() {
+        let t = TestRepo::new();
+        t.commit("a.txt", "one\ntwo\nthree\nfour\n", "add a");
+        let edit = t.commit("a.txt", "one\n2\nthree\nfour\nfive\n", "edit a");
+
+        let table =
+            Python::with_gil(|py| export_file_stats(py, t.path().to_str().unwrap(), None).unwrap());
+        assert_eq!(table.schema, file_stats_schema());
+        let hashes = column::<StringArray>(&table, "commit_hash");
+        let statuses = column::<StringArray>(&table, "status");
+        let additions = column::<UInt64Array>(&table, "additions");
+        let deletions = column::<UInt64Array>(&table, "deletions");
+        let old_paths = column::<StringArray>(&table, "old_path");
+
+        let mut rows: Vec<(&str, u64, u64)> = (0..hashes.len())
+            .map(|i| {
+                assert_eq!(column::<StringArray>(&table, "path").value(i), "a.txt");
+                assert!(old_paths.is_null(i));
+                (statuses.value(i), additions.value(i), deletions.value(i))
+            })
+            .collect();
+        rows.sort();
+        assert_eq!(rows, vec![("added", 4, 0), ("modified", 2, 1)]);
+        let edited = (0..hashes.len())
+            .find(|&i| hashes.value(i) == edit.to_string())
+            .unwrap();
+        assert_eq!(statuses.value(edited), "modified");
+    }
+
+    #[test]
+    fn export_blame_keeps_rows_and_reports_failures() {
+        let t = TestRepo::new();
+        let id = t.commit("a.txt", "one\ntwo\n", "add a");
+        let good = t.path().join("a.txt").to_string_lossy().into_owned();
+        let missing = t.path().join("missing.txt").to_string_lossy().into_owned();
+
+        let table = Python::with_gil(|py| {
+            export_blame(py, vec![good.clone(), missing.clone()], None).unwrap()
+        });
+        assert_eq!(table.schema, blame_schema());
+        assert_eq!(table.batches[0].num_rows(), 2);
+        let paths = column::<StringArray>(&table, "path");
+        let contents = column::<StringArray>(&table, "content");
+        let lines = column::<UInt32Array>(&table, "line_number");
+        let commits = column::<StringArray>(&table, "commit_hash");
+        for (i, content) in ["one", "two"].into_iter().enumerate() {
+            assert_eq!(paths.value(i), good);
+            assert_eq!(lines.value(i), i as u32 + 1);
+            assert_eq!(contents.value(i), content);
+            assert_eq!(commits.value(i), id.to_string());
+        }
+        assert_eq!(table.errors.len(), 1);
+        assert_eq!(table.errors[0].path, missing);
+    }
+}
@@ -0,0 +1,6 @@
+pub mod logic;
+pub mod model;
+
+pub use logic::{export_blame, export_commits, export_file_stats};
+
+pub use model::ArrowTable;
@@ -0,0 +1,240 @@
+use crate::commits::BlameError;
+use arrow::array::{Array, ArrayRef, ListArray, RecordBatch, RecordBatchIterator, StringArray};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use arrow::error::ArrowError;
+use arrow::ffi::FFI_ArrowSchema;
+use arrow::ffi_stream::FFI_ArrowArrayStream;
+use parquet::arrow::ArrowWriter;
+use pyo3::exceptions::{PyIOError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyCapsule;
+use std::ffi::CString;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::sync::Arc;
+
+/// A set of Arrow record batches produced in Rust.
+///
+/// The table implements the Arrow PyCapsule interface, so `pyarrow.table(t)`
+/// or `polars.DataFrame(t)` take the buffers without copying, and it can be
+/// written to Parquet, CSV or JSON Lines without going through Python.
+/// Inputs that could not be exported are listed in `errors` rather than
+/// failing the whole table.
+#[pyclass(module = "rustygit")]
+pub struct ArrowTable {
+    pub schema: SchemaRef,
+    pub batches: Vec<RecordBatch>,
+    #[pyo3(get)]
+    pub errors: Vec<BlameError>,
+}
+
+fn arrow_err_to_py_err(err: ArrowError) -> PyErr {
+    match err {
+        ArrowError::IoError(msg, _) => PyIOError::new_err(format!("Arrow error: {}", msg)),
+        other => PyValueError::new_err(format!("Arrow error: {}", other)),
+    }
+}
+
+fn io_err_to_py_err(err: std::io::Error) -> PyErr {
+    PyIOError::new_err(format!("{}", err))
+}
+
+#[pymethods]
+impl ArrowTable {
+    /// Total number of rows across all batches
+    #[getter]
+    fn num_rows(&self) -> usize {
+        self.batches.iter().map(|b| b.num_rows()).sum()
+    }
+
+    /// Column names, in schema order
+    #[getter]
+    fn column_names(&self) -> Vec<String> {
+        self.schema
+            .fields()
+            .iter()
+            .map(|f| f.name().clone())
+            .collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.num_rows()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ArrowTable(num_rows={}, columns={:?}, errors={})",
+            self.num_rows(),
+            self.column_names(),
+            self.errors.len()
+        )
+    }
+
+    /// Export the schema as an `arrow_schema` PyCapsule
+    fn __arrow_c_schema__<'py>(&self, py: Python<'py>) -> PyResult<&'py PyCapsule> {
+        let schema =
+            FFI_ArrowSchema::try_from(self.schema.as_ref()).map_err(arrow_err_to_py_err)?;
+        PyCapsule::new(py, schema, Some(CString::new("arrow_schema").unwrap()))
+    }
+
+    /// Export the batches as an `arrow_array_stream` PyCapsule.
+    ///
+    /// A requested schema is accepted for protocol compatibility but ignored;
+    /// the data is always exported with its own schema.
+    #[pyo3(signature = (requested_schema=None))]
+    fn __arrow_c_stream__<'py>(
+        &self,
+        py: Python<'py>,
+        requested_schema: Option<PyObject>,
+    ) -> PyResult<&'py PyCapsule> {
+        let _ = requested_schema;
+        let reader = RecordBatchIterator::new(
+            self.batches.clone().into_iter().map(Ok),
+            self.schema.clone(),
+        );
+        let stream = FFI_ArrowArrayStream::new(Box::new(reader));
+        PyCapsule::new(
+            py,
+            stream,
+            Some(CString::new("arrow_array_stream").unwrap()),
+        )
+    }
+
+    /// Convert to a `pyarrow.Table` (requires pyarrow >= 14)
+    fn to_pyarrow(slf: &PyCell<Self>, py: Python) -> PyResult<PyObject> {
+        let pyarrow = py.import("pyarrow")?;
+        Ok(pyarrow.call_method1("table", (slf,))?.to_object(py))
+    }
+
+    /// Write the table to a Parquet file (Snappy-compressed)
+    fn write_parquet(&self, path: &str) -> PyResult<()> {
+        let file = File::create(path).map_err(io_err_to_py_err)?;
+        let props = parquet::file::properties::WriterProperties::builder()
+            .set_compression(parquet::basic::Compression::SNAPPY)
+            .build();
+        let mut writer = ArrowWriter::try_new(file, self.schema.clone(), Some(props))
+            .map_err(|e| PyIOError::new_err(format!("Parquet error: {}", e)))?;
+        for batch in &self.batches {
+            writer
+                .write(batch)
+                .map_err(|e| PyIOError::new_err(format!("Parquet error: {}", e)))?;
+        }
+        writer
+            .close()
+            .map_err(|e| PyIOError::new_err(format!("Parquet error: {}", e)))?;
+        Ok(())
+    }
+
+    /// Write the table to a CSV file with a header row.
+    ///
+    /// List columns are written as their items joined by single spaces.
+    fn write_csv(&self, path: &str) -> PyResult<()> {
+        let file = File::create(path).map_err(io_err_to_py_err)?;
+        let mut writer = arrow::csv::WriterBuilder::new()
+            .with_header(true)
+            .build(BufWriter::new(file));
+        for batch in &self.batches {
+            let batch = flatten_lists(batch).map_err(arrow_err_to_py_err)?;
+            writer.write(&batch).map_err(arrow_err_to_py_err)?;
+        }
+        writer.into_inner().flush().map_err(io_err_to_py_err)
+    }
+
+    /// Write the table to a JSON Lines file, one object per row
+    fn write_jsonl(&self, path: &str) -> PyResult<()> {
+        let file = File::create(path).map_err(io_err_to_py_err)?;
+        let mut writer = arrow::json::LineDelimitedWriter::new(BufWriter::new(file));
+        for batch in &self.batches {
+            writer.write(batch).map_err(arrow_err_to_py_err)?;
+        }
+        writer.finish().map_err(arrow_err_to_py_err)?;
+        writer.into_inner().flush().map_err(io_err_to_py_err)
+    }
+}
+
+/// Replace list-of-string columns by space-joined strings, for CSV output.
+fn flatten_lists(batch: &RecordBatch) -> Result<RecordBatch, ArrowError> {
+    let mut fields = Vec::with_capacity(batch.num_columns());
+    let mut columns: Vec<ArrayRef> = Vec::with_capacity(batch.num_columns());
+    for (field, column) in batch.schema().fields().iter().zip(batch.columns()) {
+        match column.as_any().downcast_ref::<ListArray>() {
+            Some(list) => {
+                let joined: StringArray = (0..list.len())
+                    .map(|i| {
+                        if list.is_null(i) {
+                            return None;
+                        }
+                        let items = list.value(i);
+                        let items = items.as_any().downcast_ref::<StringArray>()?;
+                        Some(items.iter().flatten().collect::<Vec<_>>().join(" "))
+                    })
+                    .collect();
+                fields.push(Field::new(field.name(), DataType::Utf8, true));
+                columns.push(Arc::new(joined));
+            }
+            None => {
+                fields.push(field.as_ref().clone());
+                columns.push(column.clone());
+            }
+        }
+    }
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::logic::export_commits;
+    use crate::test_utils::TestRepo;
+    use std::fs;
+
+    fn two_commit_table(t: &TestRepo) -> ArrowTable {
+        t.commit("a.txt", "one\n", "first");
+        t.commit("a.txt", "two\n", "second");
+        Python::with_gil(|py| export_commits(py, t.path().to_str().unwrap(), None, None).unwrap())
+    }
+
+    #[test]
+    fn write_csv_writes_a_header_and_every_row() {
+        let t = TestRepo::new();
+        let table = two_commit_table(&t);
+        let path = t.sibling("commits.csv");
+
+        table.write_csv(path.to_str().unwrap()).unwrap();
+        let csv = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], table.column_names().join(","));
+        assert_eq!(lines.len(), 3);
+        assert!(csv.contains("second"));
+    }
+
+    #[test]
+    fn write_jsonl_writes_one_object_per_row() {
+        let t = TestRepo::new();
+        let table = two_commit_table(&t);
+        let path = t.sibling("commits.jsonl");
+
+        table.write_jsonl(path.to_str().unwrap()).unwrap();
+        let jsonl = fs::read_to_string(&path).unwrap();
+        assert_eq!(jsonl.lines().count(), 2);
+        assert!(jsonl.lines().all(|line| line.starts_with("{\"hash\":")));
+    }
+
+    #[test]
+    fn write_errors_are_io_errors() {
+        let t = TestRepo::new();
+        let table = two_commit_table(&t);
+        let path = t.sibling("missing-dir").join("out");
+        let path = path.to_str().unwrap();
+
+        Python::with_gil(|py| {
+            for err in [
+                table.write_csv(path).unwrap_err(),
+                table.write_jsonl(path).unwrap_err(),
+                table.write_parquet(path).unwrap_err(),
+            ] {
+                assert!(err.is_instance_of::<PyIOError>(py));
+            }
+        });
+    }
+}
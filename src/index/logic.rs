@@ -0,0 +1,468 @@
+use crate::repo::Repo;
+use crate::utils::{git_err_to_py_err, resolve_commit};
+use git2::{ErrorCode, Index, IndexConflict, IndexEntry, IndexTime, Oid};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use std::path::Path;
+
+use super::model::{Conflict, Index as PyIndex, IndexEntry as PyIndexEntry};
+
+/// Ancestor, our and their content of a conflicted path.
+type ConflictContent = (
+    Option<Py<PyBytes>>,
+    Option<Py<PyBytes>>,
+    Option<Py<PyBytes>>,
+);
+
+/// The conflicts recorded in `index`, in path order.
+pub fn index_conflicts(index: &Index) -> Result<Vec<Conflict>, git2::Error> {
+    let side = |entry: &Option<IndexEntry>| {
+        entry.as_ref().map(|entry| {
+            (
+                String::from_utf8_lossy(&entry.path).into_owned(),
+                entry.id.to_string(),
+            )
+        })
+    };
+
+    let mut conflicts = Vec::new();
+    for conflict in index.conflicts()? {
+        let conflict = conflict?;
+        let (ancestor_path, ancestor_id) = side(&conflict.ancestor).unzip();
+        let (our_path, our_id) = side(&conflict.our).unzip();
+        let (their_path, their_id) = side(&conflict.their).unzip();
+        let path = our_path
+            .clone()
+            .or_else(|| their_path.clone())
+            .or_else(|| ancestor_path.clone())
+            .unwrap_or_default();
+        conflicts.push(Conflict {
+            path,
+            ancestor_path,
+            ancestor_id,
+            our_path,
+            our_id,
+            their_path,
+            their_id,
+        });
+    }
+    Ok(conflicts)
+}
+
+/// Reload the repository's index from disk if another process changed it
+/// since it was loaded.
+pub fn refresh_index(repo: &git2::Repository) -> Result<(), git2::Error> {
+    repo.index()?.read(false)
+}
+
+/// Stage-0 index entry for `path` pointing at the blob `id`.
+pub fn resolved_entry(path: &str, mode: u32, id: Oid, size: u32) -> IndexEntry {
+    IndexEntry {
+        ctime: IndexTime::new(0, 0),
+        mtime: IndexTime::new(0, 0),
+        dev: 0,
+        ino: 0,
+        mode,
+        uid: 0,
+        gid: 0,
+        file_size: size,
+        id,
+        flags: path.len().min(0xfff) as u16,
+        flags_extended: 0,
+        path: path.as_bytes().to_vec(),
+    }
+}
+
+fn ensure_resolved(index: &Index) -> PyResult<()> {
+    if index.has_conflicts() {
+        return Err(PyValueError::new_err(
+            "Cannot write an index with unresolved conflicts",
+        ));
+    }
+    Ok(())
+}
+
+impl PyIndex {
+    /// The conflict recorded for `path`, or a `ValueError` if there is none.
+    fn conflict(&self, path: &str) -> PyResult<IndexConflict> {
+        match self.inner.conflict_get(Path::new(path)) {
+            Ok(conflict) => Ok(conflict),
+            Err(e) if e.code() == ErrorCode::NotFound => Err(PyValueError::new_err(format!(
+                "'{}' is not conflicted",
+                path
+            ))),
+            Err(e) => Err(git_err_to_py_err(e)),
+        }
+    }
+
+    /// Replace the conflict on `path` with `side`, or remove the path when
+    /// that side deleted it.
+    fn take_side(&mut self, path: &str, side: Option<IndexEntry>) -> PyResult<()> {
+        self.inner
+            .conflict_remove(Path::new(path))
+            .map_err(git_err_to_py_err)?;
+        match side {
+            Some(entry) => {
+                self.inner
+                    .add(&resolved_entry(path, entry.mode, entry.id, entry.file_size))
+            }
+            None => self.inner.remove_path(Path::new(path)),
+        }
+        .map_err(git_err_to_py_err)
+    }
+}
+
+#[pymethods]
+impl PyIndex {
+    /// The conflicted paths, in path order
+    fn conflicts(&self) -> PyResult<Vec<Conflict>> {
+        index_conflicts(&self.inner).map_err(git_err_to_py_err)
+    }
+
+    /// Content of the ancestor, our and their sides of the conflict on
+    /// `path`; a side is `None` where it is missing from the conflict
+    fn conflict_content(&self, py: Python, path: &str) -> PyResult<ConflictContent> {
+        let conflict = self.conflict(path)?;
+        let repo = self.repo.borrow(py);
+        let read = |side: Option<IndexEntry>| -> PyResult<Option<Py<PyBytes>>> {
+            side.map(|entry| {
+                let blob = repo.inner.find_blob(entry.id).map_err(git_err_to_py_err)?;
+                Ok(PyBytes::new(py, blob.content()).into())
+            })
+            .transpose()
+        };
+        Ok((
+            read(conflict.ancestor)?,
+            read(conflict.our)?,
+            read(conflict.their)?,
+        ))
+    }
+
+    /// Mark the conflict on `path` resolved with `content`, or with the
+    /// path deleted when `content` is `None`
+    fn resolve(&mut self, py: Python, path: &str, content: Option<&[u8]>) -> PyResult<()> {
+        let conflict = self.conflict(path)?;
+        let side = match content {
+            Some(content) => {
+                let mode = conflict
+                    .our
+                    .as_ref()
+                    .or(conflict.their.as_ref())
+                    .or(conflict.ancestor.as_ref())
+                    .map_or(0o100644, |entry| entry.mode);
+                let id = self
+                    .repo
+                    .borrow(py)
+                    .inner
+                    .blob(content)
+                    .map_err(git_err_to_py_err)?;
+                Some(resolved_entry(path, mode, id, content.len() as u32))
+            }
+            None => None,
+        };
+        self.take_side(path, side)
+    }
+
+    /// Resolve the conflict on `path` with our side
+    fn take_ours(&mut self, path: &str) -> PyResult<()> {
+        let conflict = self.conflict(path)?;
+        self.take_side(path, conflict.our)
+    }
+
+    /// Resolve the conflict on `path` with their side
+    fn take_theirs(&mut self, path: &str) -> PyResult<()> {
+        let conflict = self.conflict(path)?;
+        self.take_side(path, conflict.their)
+    }
+
+    /// Every entry, including the stages of conflicted paths, in path order
+    fn entries(&self) -> Vec<PyIndexEntry> {
+        self.inner
+            .iter()
+            .map(|entry| PyIndexEntry {
+                path: String::from_utf8_lossy(&entry.path).into_owned(),
+                mode: entry.mode,
+                id: entry.id.to_string(),
+                stage: (entry.flags >> 12) & 0x3,
+                size: entry.file_size,
+            })
+            .collect()
+    }
+
+    /// Save the index back to the repository's index file. Indexes held
+    /// only in memory have no file and raise an error.
+    fn write(&mut self) -> PyResult<()> {
+        self.inner.write().map_err(git_err_to_py_err)
+    }
+
+    /// Write the index as a tree object, returning its id
+    fn write_tree(&mut self, py: Python) -> PyResult<String> {
+        ensure_resolved(&self.inner)?;
+        let repo = self.repo.borrow(py);
+        let tree_id = self
+            .inner
+            .write_tree_to(&repo.inner)
+            .map_err(git_err_to_py_err)?;
+        Ok(tree_id.to_string())
+    }
+
+    /// Write the index as a commit without updating any ref, returning its
+    /// id. `parents` default to the commits that were merged.
+    #[pyo3(signature = (message, parents=None))]
+    fn write_commit(
+        &mut self,
+        py: Python,
+        message: &str,
+        parents: Option<Vec<String>>,
+    ) -> PyResult<String> {
+        ensure_resolved(&self.inner)?;
+        let repo = self.repo.borrow(py);
+        let tree_id = self
+            .inner
+            .write_tree_to(&repo.inner)
+            .map_err(git_err_to_py_err)?;
+        let tree = repo.inner.find_tree(tree_id).map_err(git_err_to_py_err)?;
+        let parents = parents
+            .unwrap_or_else(|| self.parents.clone())
+            .iter()
+            .map(|rev| resolve_commit(&repo.inner, rev).and_then(|oid| repo.inner.find_commit(oid)))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(git_err_to_py_err)?;
+        let parents: Vec<_> = parents.iter().collect();
+        let signature = repo.inner.signature().map_err(git_err_to_py_err)?;
+        let commit_id = repo
+            .inner
+            .commit(None, &signature, &signature, message, &tree, &parents)
+            .map_err(git_err_to_py_err)?;
+        Ok(commit_id.to_string())
+    }
+}
+
+#[pymethods]
+impl Repo {
+    /// The repository's index, reloaded from disk. Changes are saved with
+    /// `Index.write`.
+    fn index(slf: PyRef<'_, Self>) -> PyResult<PyIndex> {
+        let index = slf.inner.index().map_err(git_err_to_py_err)?;
+        refresh_index(&slf.inner).map_err(git_err_to_py_err)?;
+        Ok(PyIndex {
+            inner: index,
+            repo: slf.into(),
+            parents: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestRepo;
+
+    /// Remove `path` from the index and commit that on top of `HEAD`.
+    fn commit_removal(t: &TestRepo, path: &str, message: &str) {
+        let repo = t.git();
+        let mut index = repo.index().unwrap();
+        index.remove_path(Path::new(path)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = repo.signature().unwrap();
+        let tip = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &[&tip],
+        )
+        .unwrap();
+        std::fs::remove_file(t.path().join(path)).unwrap();
+    }
+
+    /// `file.txt` changed on both sides and `gone.txt` changed on the
+    /// default branch but deleted on `topic`, which also adds `new.txt`.
+    /// Leaves the default branch checked out.
+    fn conflicted() -> TestRepo {
+        let t = TestRepo::new();
+        t.commit("file.txt", "base\n", "add file");
+        let base = t.commit("gone.txt", "gone\n", "add gone");
+        let main = t.head_name().unwrap();
+        t.checkout_new_branch("topic", base);
+        t.commit("file.txt", "topic\n", "change file on topic");
+        t.commit("new.txt", "new\n", "add new");
+        commit_removal(&t, "gone.txt", "remove gone");
+        t.checkout(&main);
+        t.commit("file.txt", "main\n", "change file on main");
+        t.commit("gone.txt", "changed\n", "change gone on main");
+        t
+    }
+
+    fn merged(t: &TestRepo, py: Python) -> PyIndex {
+        let repo = t.git();
+        let ours = repo.head().unwrap().peel_to_commit().unwrap();
+        let theirs = repo
+            .revparse_single("topic")
+            .unwrap()
+            .peel_to_commit()
+            .unwrap();
+        PyIndex {
+            inner: repo.merge_commits(&ours, &theirs, None).unwrap(),
+            repo: t.py_repo(py),
+            parents: vec![ours.id().to_string(), theirs.id().to_string()],
+        }
+    }
+
+    fn bytes(py: Python, side: Option<Py<PyBytes>>) -> Option<Vec<u8>> {
+        side.map(|side| side.as_ref(py).as_bytes().to_vec())
+    }
+
+    #[test]
+    fn conflicts_and_their_content() {
+        let t = conflicted();
+        Python::with_gil(|py| {
+            let index = merged(&t, py);
+            let paths: Vec<_> = index
+                .conflicts()
+                .unwrap()
+                .into_iter()
+                .map(|conflict| conflict.path)
+                .collect();
+            assert_eq!(paths, vec!["file.txt", "gone.txt"]);
+
+            let (ancestor, ours, theirs) = index.conflict_content(py, "file.txt").unwrap();
+            assert_eq!(bytes(py, ancestor), Some(b"base\n".to_vec()));
+            assert_eq!(bytes(py, ours), Some(b"main\n".to_vec()));
+            assert_eq!(bytes(py, theirs), Some(b"topic\n".to_vec()));
+            let (_, ours, theirs) = index.conflict_content(py, "gone.txt").unwrap();
+            assert_eq!(bytes(py, ours), Some(b"changed\n".to_vec()));
+            assert_eq!(bytes(py, theirs), None);
+
+            let stages: Vec<_> = index
+                .entries()
+                .into_iter()
+                .map(|entry| (entry.path, entry.stage))
+                .collect();
+            let expected = [
+                ("file.txt", 1),
+                ("file.txt", 2),
+                ("file.txt", 3),
+                ("gone.txt", 1),
+                ("gone.txt", 2),
+                ("new.txt", 0),
+            ];
+            assert_eq!(
+                stages,
+                expected.map(|(path, stage)| (path.to_string(), stage))
+            );
+
+            let err = index.conflict_content(py, "new.txt").unwrap_err();
+            assert!(err.is_instance_of::<PyValueError>(py));
+        });
+    }
+
+    #[test]
+    fn resolving_then_writing_a_commit() {
+        let t = conflicted();
+        let head = t.head();
+        Python::with_gil(|py| {
+            let mut index = merged(&t, py);
+            let err = index.write_tree(py).unwrap_err();
+            assert!(err.is_instance_of::<PyValueError>(py));
+
+            index.resolve(py, "file.txt", Some(b"merged\n")).unwrap();
+            index.take_theirs("gone.txt").unwrap();
+            assert!(!index.inner.has_conflicts());
+            let err = index.take_ours("file.txt").unwrap_err();
+            assert!(err.is_instance_of::<PyValueError>(py));
+
+            let commit_id = index.write_commit(py, "merge topic", None).unwrap();
+            let commit = t
+                .git()
+                .find_commit(Oid::from_str(&commit_id).unwrap())
+                .unwrap();
+            assert_eq!(commit.parent_ids().len(), 2);
+            assert_eq!(commit.parent_id(0).unwrap(), head);
+            let tree = commit.tree().unwrap();
+            let names: Vec<_> = tree
+                .iter()
+                .map(|entry| entry.name().unwrap().to_string())
+                .collect();
+            assert_eq!(names, vec!["file.txt", "new.txt"]);
+            let blob = t.git().find_blob(tree.get(0).unwrap().id()).unwrap();
+            assert_eq!(blob.content(), b"merged\n");
+            assert_eq!(index.write_tree(py).unwrap(), tree.id().to_string());
+
+            // Indexes held in memory have no file to write to.
+            assert!(index.write().is_err());
+        });
+        // No ref moved.
+        assert_eq!(t.head(), head);
+    }
+
+    #[test]
+    fn take_ours_and_resolve_to_deletion() {
+        let t = conflicted();
+        Python::with_gil(|py| {
+            let mut index = merged(&t, py);
+            index.take_ours("file.txt").unwrap();
+            index.resolve(py, "gone.txt", None).unwrap();
+
+            let entries: Vec<_> = index
+                .entries()
+                .into_iter()
+                .map(|entry| (entry.path, entry.stage))
+                .collect();
+            assert_eq!(
+                entries,
+                vec![("file.txt".to_string(), 0), ("new.txt".to_string(), 0)]
+            );
+            let file = index.inner.get_path(Path::new("file.txt"), 0).unwrap();
+            assert_eq!(file.id, t.git().blob(b"main\n").unwrap());
+
+            let parent = index.parents[1].clone();
+            let commit_id = index
+                .write_commit(py, "keep ours", Some(vec![parent.clone()]))
+                .unwrap();
+            let commit = t
+                .git()
+                .find_commit(Oid::from_str(&commit_id).unwrap())
+                .unwrap();
+            assert_eq!(
+                commit
+                    .parent_ids()
+                    .map(|id| id.to_string())
+                    .collect::<Vec<_>>(),
+                vec![parent]
+            );
+        });
+    }
+
+    #[test]
+    fn repository_index_is_written_back() {
+        let t = conflicted();
+        let topic = t
+            .git()
+            .find_annotated_commit(t.git().revparse_single("topic").unwrap().id())
+            .unwrap();
+        t.git().merge(&[&topic], None, None).unwrap();
+
+        Python::with_gil(|py| {
+            let repo = t.py_repo(py);
+            let mut index = Repo::index(repo.borrow(py)).unwrap();
+            assert_eq!(index.conflicts().unwrap().len(), 2);
+            assert!(index.parents.is_empty());
+            index.take_theirs("file.txt").unwrap();
+            index.take_ours("gone.txt").unwrap();
+            index.write().unwrap();
+
+            let reloaded = Repo::index(repo.borrow(py)).unwrap();
+            assert!(reloaded.conflicts().unwrap().is_empty());
+            let file = reloaded.inner.get_path(Path::new("file.txt"), 0).unwrap();
+            assert_eq!(file.id, t.git().blob(b"topic\n").unwrap());
+        });
+        let mut on_disk = t.git().index().unwrap();
+        on_disk.read(true).unwrap();
+        assert!(!on_disk.has_conflicts());
+    }
+}
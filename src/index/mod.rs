@@ -0,0 +1,6 @@
+pub mod logic;
+pub mod model;
+
+pub use logic::{index_conflicts, refresh_index, resolved_entry};
+
+pub use model::{Conflict, Index, IndexEntry};
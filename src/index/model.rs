@@ -0,0 +1,95 @@
+use crate::repo::Repo;
+use crate::utils::py_model;
+use pyo3::prelude::*;
+
+/// A path left conflicted in the index by a merge, cherry-pick, revert or
+/// stash application. Sides missing from the conflict are `None`, e.g. the
+/// ancestor when both sides added the file.
+#[pyclass(module = "rustygit")]
+#[derive(Clone, Debug)]
+pub struct Conflict {
+    #[pyo3(get)]
+    pub path: String,
+    #[pyo3(get)]
+    pub ancestor_path: Option<String>,
+    #[pyo3(get)]
+    pub ancestor_id: Option<String>,
+    #[pyo3(get)]
+    pub our_path: Option<String>,
+    #[pyo3(get)]
+    pub our_id: Option<String>,
+    #[pyo3(get)]
+    pub their_path: Option<String>,
+    #[pyo3(get)]
+    pub their_id: Option<String>,
+}
+
+py_model!(
+    Conflict,
+    fields: [path, ancestor_path, ancestor_id, our_path, our_id, their_path, their_id],
+    key: [path, ancestor_id, our_id, their_id],
+    repr: [path],
+);
+
+/// A stage entry of an index: stage 0 for a merged path, 1-3 for the
+/// ancestor, our and their sides of a conflict.
+#[pyclass(module = "rustygit")]
+#[derive(Clone, Debug)]
+pub struct IndexEntry {
+    #[pyo3(get)]
+    pub path: String,
+    #[pyo3(get)]
+    pub mode: u32,
+    #[pyo3(get)]
+    pub id: String,
+    #[pyo3(get)]
+    pub stage: u16,
+    /// Size recorded for the working tree file; 0 for entries that never
+    /// had one, such as merge results
+    #[pyo3(get)]
+    pub size: u32,
+}
+
+py_model!(
+    IndexEntry,
+    fields: [path, mode, id, stage, size],
+    key: [path, id, stage],
+    repr: [path, id, stage],
+);
+
+/// A git index: the repository's own (`Repo.index`) or one held in memory,
+/// such as the result of `Repo.merge_commits`.
+#[pyclass(unsendable, module = "rustygit")]
+pub struct Index {
+    pub(crate) inner: git2::Index,
+    /// Repository the index's objects are read from and written to
+    pub(crate) repo: Py<Repo>,
+    /// Parents used by `write_commit` when none are given
+    #[pyo3(get)]
+    pub parents: Vec<String>,
+}
+
+#[pymethods]
+impl Index {
+    /// Whether any path is conflicted
+    #[getter]
+    fn has_conflicts(&self) -> bool {
+        self.inner.has_conflicts()
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Index(entries={}, has_conflicts={})",
+            self.inner.len(),
+            if self.inner.has_conflicts() {
+                "True"
+            } else {
+                "False"
+            }
+        )
+    }
+}
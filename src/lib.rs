@@ -1,32 +1,90 @@
 #![allow(dead_code)]
+// pyo3 0.20's `#[new]`, `#[pymethods]` and `#[pymodule]` expansions define
+// impls inside functions, which newer compilers flag as non-local.
+#![allow(non_local_definitions)]
 
 use pyo3::prelude::*;
 
 mod branch;
 mod commits;
+mod config;
+mod contributors;
+mod export;
+mod index;
+mod operations;
+mod ownership;
+mod reflog;
 mod repo;
+mod revision;
+mod search;
+mod stash;
+mod submodule;
+#[cfg(test)]
+mod test_utils;
+mod tree;
 mod utils;
+mod worktree;
 
 #[pymodule]
 fn rustygit(py: Python, m: &PyModule) -> PyResult<()> {
     // Register top-level classes
     m.add_class::<repo::Repo>()?;
     m.add_class::<branch::Branch>()?;
+    m.add_class::<tree::TreeEntry>()?;
+    m.add_class::<tree::Blob>()?;
+    m.add_class::<tree::BlobIter>()?;
     m.add_class::<commits::Commit>()?;
+    m.add_class::<commits::Identity>()?;
+    m.add_class::<commits::Trailer>()?;
+    m.add_class::<commits::ConventionalCommit>()?;
+    m.add_class::<contributors::Contributor>()?;
     m.add_class::<commits::DiffEntry>()?;
+    m.add_class::<commits::DiffHunk>()?;
     m.add_class::<commits::BlameLine>()?;
+    m.add_class::<commits::BlameResult>()?;
+    m.add_class::<commits::BlameError>()?;
+    m.add_class::<commits::BlameErrorKind>()?;
+    m.add_class::<export::ArrowTable>()?;
+    m.add_class::<revision::GitObject>()?;
+    m.add_class::<revision::RevRange>()?;
+    m.add_class::<search::PickaxeMatch>()?;
+    m.add_class::<search::GrepMatch>()?;
+    m.add_class::<index::Conflict>()?;
+    m.add_class::<index::Index>()?;
+    m.add_class::<index::IndexEntry>()?;
+    m.add_class::<stash::Stash>()?;
+    m.add_class::<submodule::Submodule>()?;
+    m.add_class::<worktree::Worktree>()?;
+    m.add_class::<config::Config>()?;
+    m.add_class::<config::ConfigEntry>()?;
+    m.add_class::<reflog::ReflogEntry>()?;
+    m.add_class::<ownership::OwnershipResult>()?;
+    m.add_class::<operations::RevertResult>()?;
+    m.add_class::<operations::CherryPickResult>()?;
+    m.add_class::<operations::RebaseResult>()?;
 
     // Add top-level functions
     m.add_function(wrap_pyfunction!(commits::get_commit_history, m)?)?;
     m.add_function(wrap_pyfunction!(commits::get_file_change_summary, m)?)?;
     m.add_function(wrap_pyfunction!(commits::get_file_blame, m)?)?;
     m.add_function(wrap_pyfunction!(commits::get_blame_for_files, m)?)?;
+    m.add_function(wrap_pyfunction!(ownership::code_ownership, m)?)?;
+    m.add_function(wrap_pyfunction!(export::export_commits, m)?)?;
+    m.add_function(wrap_pyfunction!(export::export_file_stats, m)?)?;
+    m.add_function(wrap_pyfunction!(export::export_blame, m)?)?;
 
     // `commits` submodule (optional alternative access path)
     let commit_mod = PyModule::new(py, "commits")?;
     commit_mod.add_class::<commits::Commit>()?;
+    commit_mod.add_class::<commits::Identity>()?;
+    commit_mod.add_class::<commits::Trailer>()?;
+    commit_mod.add_class::<commits::ConventionalCommit>()?;
     commit_mod.add_class::<commits::DiffEntry>()?;
+    commit_mod.add_class::<commits::DiffHunk>()?;
     commit_mod.add_class::<commits::BlameLine>()?;
+    commit_mod.add_class::<commits::BlameResult>()?;
+    commit_mod.add_class::<commits::BlameError>()?;
+    commit_mod.add_class::<commits::BlameErrorKind>()?;
     commit_mod.add_function(wrap_pyfunction!(commits::get_commit_history, commit_mod)?)?;
     commit_mod.add_function(wrap_pyfunction!(
         commits::get_file_change_summary,
@@ -0,0 +1,1256 @@
+use crate::index::{index_conflicts, refresh_index, resolved_entry, Index as PyIndex};
+use crate::repo::Repo;
+use crate::utils::{git_err_to_py_err, resolve_commit};
+use git2::build::CheckoutBuilder;
+use git2::{
+    CherrypickOptions, ErrorCode, Index, Oid, Rebase, RebaseOptions, Repository, RepositoryState,
+    ResetType, RevertOptions, Signature, StatusOptions,
+};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::model::{CherryPickResult, PlanStep, RebaseResult, RevertResult};
+
+fn parse_reset_mode(mode: &str) -> PyResult<ResetType> {
+    match mode {
+        "soft" => Ok(ResetType::Soft),
+        "mixed" => Ok(ResetType::Mixed),
+        "hard" => Ok(ResetType::Hard),
+        other => Err(PyValueError::new_err(format!(
+            "Unknown reset mode '{}', expected 'soft', 'mixed' or 'hard'",
+            other
+        ))),
+    }
+}
+
+/// Commit the repository's index on top of `HEAD`, logging the update as
+/// `<reflog_action>: <subject>` like git does.
+///
+/// `author` defaults to the configured identity, which is always the
+/// committer.
+pub fn commit_index(
+    repo: &git2::Repository,
+    message: &str,
+    author: Option<&Signature>,
+    reflog_action: &str,
+) -> Result<Oid, git2::Error> {
+    let committer = repo.signature()?;
+    let tree = repo.find_tree(repo.index()?.write_tree()?)?;
+    let mut head = repo.head()?;
+    let parent = head.peel_to_commit()?;
+    let new_id = repo.commit(
+        None,
+        author.unwrap_or(&committer),
+        &committer,
+        message,
+        &tree,
+        &[&parent],
+    )?;
+    let subject = message.lines().next().unwrap_or("");
+    head.set_target(new_id, &format!("{}: {}", reflog_action, subject))?;
+    Ok(new_id)
+}
+
+/// Check out `rev`, attaching `HEAD` to it when it names a branch.
+fn switch_to(repo: &Repository, rev: &str) -> Result<(), git2::Error> {
+    let (object, reference) = repo.revparse_ext(rev)?;
+    let commit = object.peel_to_commit()?;
+    repo.checkout_tree(commit.as_object(), Some(CheckoutBuilder::new().safe()))?;
+    match reference.as_ref().and_then(|r| r.name()) {
+        Some(name) if reference.as_ref().is_some_and(|r| r.is_branch()) => repo.set_head(name),
+        _ => repo.set_head_detached(commit.id()),
+    }
+}
+
+/// The commit `HEAD` points at and the branch it is attached to, if any.
+fn head_position(repo: &Repository) -> Result<(Oid, Option<String>), git2::Error> {
+    let head = repo.head()?;
+    let name = if head.is_branch() {
+        head.name().map(String::from)
+    } else {
+        None
+    };
+    Ok((head.peel_to_commit()?.id(), name))
+}
+
+/// Check out `commit` and point `HEAD` back at `head_name`, or detach it at
+/// `commit` when there is no branch.
+fn restore_head(
+    repo: &Repository,
+    commit: Oid,
+    head_name: Option<&str>,
+) -> Result<(), git2::Error> {
+    let commit = repo.find_commit(commit)?;
+    repo.checkout_tree(commit.as_object(), Some(CheckoutBuilder::new().safe()))?;
+    match head_name {
+        Some(name) => repo.set_head(name),
+        None => repo.set_head_detached(commit.id()),
+    }
+}
+
+/// Commit the result of picking `picked`, keeping its author and message.
+/// Returns `None`, without committing, when the pick changed nothing.
+fn commit_pick(repo: &Repository, picked: &git2::Commit) -> Result<Option<Oid>, git2::Error> {
+    let tree_id = repo.index()?.write_tree()?;
+    let head = repo.head()?.peel_to_commit()?;
+    let new_id = if tree_id == head.tree_id() {
+        None
+    } else {
+        Some(commit_index(
+            repo,
+            picked.message().unwrap_or(""),
+            Some(&picked.author()),
+            "cherry-pick",
+        )?)
+    };
+    repo.cleanup_state()?;
+    Ok(new_id)
+}
+
+/// Pick `commits` onto `HEAD` one at a time, stopping at the first conflict.
+fn pick_sequence(
+    repo: &Repository,
+    mut progress: CherryPickResult,
+    commits: &[Oid],
+) -> Result<CherryPickResult, git2::Error> {
+    for (i, oid) in commits.iter().enumerate() {
+        let picked = repo.find_commit(*oid)?;
+        let mut options = CherrypickOptions::new();
+        if let Some(mainline) = progress.mainline.filter(|_| picked.parent_count() > 1) {
+            options.mainline(mainline);
+        }
+        repo.cherrypick(&picked, Some(&mut options))?;
+
+        let conflicts = index_conflicts(&repo.index()?)?;
+        if !conflicts.is_empty() {
+            progress.conflicts = conflicts;
+            progress.current = Some(oid.to_string());
+            progress.remaining = commits[i + 1..].iter().map(Oid::to_string).collect();
+            return Ok(progress);
+        }
+        match commit_pick(repo, &picked)? {
+            Some(new_id) => progress.applied.push(new_id.to_string()),
+            None => progress.skipped.push(oid.to_string()),
+        }
+    }
+
+    progress.conflicts = Vec::new();
+    progress.current = None;
+    progress.remaining = Vec::new();
+    Ok(progress)
+}
+
+/// Merge the trees `ours` and `theirs` resolve to against `ancestor`, or
+/// against the empty tree when there is none.
+fn merge_tree_revs(
+    repo: &Repository,
+    ancestor: Option<&str>,
+    ours: &str,
+    theirs: &str,
+) -> Result<Index, git2::Error> {
+    let tree = |rev: &str| {
+        repo.revparse_single(rev)
+            .and_then(|object| object.peel_to_tree())
+    };
+    let ancestor = match ancestor {
+        Some(rev) => tree(rev)?,
+        None => repo.find_tree(repo.treebuilder(None)?.write()?)?,
+    };
+    repo.merge_trees(&ancestor, &tree(ours)?, &tree(theirs)?, None)
+}
+
+fn parse_oids(ids: &[String]) -> PyResult<Vec<Oid>> {
+    ids.iter()
+        .map(|id| Oid::from_str(id).map_err(git_err_to_py_err))
+        .collect()
+}
+
+#[pymethods]
+impl Repo {
+    /// Move `HEAD` to `rev`, like `git reset --soft|--mixed|--hard`.
+    #[pyo3(signature = (rev="HEAD", mode="mixed"))]
+    fn reset(&self, rev: &str, mode: &str) -> PyResult<()> {
+        let mode = parse_reset_mode(mode)?;
+        refresh_index(&self.inner).map_err(git_err_to_py_err)?;
+        let target = self
+            .inner
+            .revparse_single(rev)
+            .and_then(|obj| obj.peel(git2::ObjectType::Commit))
+            .map_err(git_err_to_py_err)?;
+        self.inner
+            .reset(&target, mode, None)
+            .map_err(git_err_to_py_err)
+    }
+
+    /// Revert `commit` on top of `HEAD`, like `git revert`.
+    ///
+    /// `mainline` picks the parent (1-based) to revert to for a merge
+    /// commit. When the revert applies cleanly it is committed unless
+    /// `commit_result` is false, in which case the changes are left staged.
+    /// On conflicts the index and working tree are left conflicted and the
+    /// repository stays in the reverting state.
+    #[pyo3(signature = (commit, mainline=None, commit_result=true))]
+    fn revert(
+        &self,
+        commit: &str,
+        mainline: Option<u32>,
+        commit_result: bool,
+    ) -> PyResult<RevertResult> {
+        refresh_index(&self.inner).map_err(git_err_to_py_err)?;
+        let oid = resolve_commit(&self.inner, commit).map_err(git_err_to_py_err)?;
+        let reverted = self.inner.find_commit(oid).map_err(git_err_to_py_err)?;
+
+        let mut options = RevertOptions::new();
+        if let Some(mainline) = mainline {
+            options.mainline(mainline);
+        }
+        self.inner
+            .revert(&reverted, Some(&mut options))
+            .map_err(git_err_to_py_err)?;
+
+        let index = self.inner.index().map_err(git_err_to_py_err)?;
+        let conflicts = index_conflicts(&index).map_err(git_err_to_py_err)?;
+        if !conflicts.is_empty() {
+            return Ok(RevertResult {
+                commit_id: None,
+                conflicts,
+            });
+        }
+
+        let commit_id = if commit_result {
+            let message = format!(
+                "Revert \"{}\"\n\nThis reverts commit {}.\n",
+                reverted.summary().unwrap_or(""),
+                reverted.id()
+            );
+            let new_id =
+                commit_index(&self.inner, &message, None, "revert").map_err(git_err_to_py_err)?;
+            Some(new_id.to_string())
+        } else {
+            None
+        };
+        if self.inner.state() == RepositoryState::Revert {
+            self.inner.cleanup_state().map_err(git_err_to_py_err)?;
+        }
+
+        Ok(RevertResult {
+            commit_id,
+            conflicts,
+        })
+    }
+
+    /// Three-way merge of the trees `ours` and `theirs` against `ancestor`
+    /// (the empty tree when `None`), in memory. The working tree, index
+    /// and refs are left alone.
+    #[pyo3(signature = (ancestor, ours, theirs))]
+    fn merge_trees(
+        slf: PyRef<'_, Self>,
+        ancestor: Option<&str>,
+        ours: &str,
+        theirs: &str,
+    ) -> PyResult<PyIndex> {
+        let merged =
+            merge_tree_revs(&slf.inner, ancestor, ours, theirs).map_err(git_err_to_py_err)?;
+        Ok(PyIndex {
+            inner: merged,
+            repo: slf.into(),
+            parents: Vec::new(),
+        })
+    }
+
+    /// Merge the commits `ours` and `theirs` from their merge base, in
+    /// memory, like `git merge-tree --write-tree`. The working tree, index
+    /// and refs are left alone.
+    fn merge_commits(slf: PyRef<'_, Self>, ours: &str, theirs: &str) -> PyResult<PyIndex> {
+        let ours = resolve_commit(&slf.inner, ours).map_err(git_err_to_py_err)?;
+        let theirs = resolve_commit(&slf.inner, theirs).map_err(git_err_to_py_err)?;
+        let merged = slf
+            .inner
+            .find_commit(ours)
+            .and_then(|ours| {
+                let theirs = slf.inner.find_commit(theirs)?;
+                slf.inner.merge_commits(&ours, &theirs, None)
+            })
+            .map_err(git_err_to_py_err)?;
+        Ok(PyIndex {
+            inner: merged,
+            repo: slf.into(),
+            parents: vec![ours.to_string(), theirs.to_string()],
+        })
+    }
+
+    /// Apply `commits` in order on top of `HEAD`, like `git cherry-pick`,
+    /// keeping each commit's author and message.
+    ///
+    /// With `onto`, that branch (or revision) is checked out first. The
+    /// sequence stops at the first conflict, leaving it in the index and
+    /// working tree; commits whose changes are already present are skipped.
+    #[pyo3(signature = (commits, onto=None, mainline=None))]
+    fn cherry_pick(
+        &self,
+        commits: &PyAny,
+        onto: Option<&str>,
+        mainline: Option<u32>,
+    ) -> PyResult<CherryPickResult> {
+        let commits: Vec<String> = match commits.extract::<String>() {
+            Ok(single) => vec![single],
+            Err(_) => commits.extract()?,
+        };
+        let oids = commits
+            .iter()
+            .map(|rev| resolve_commit(&self.inner, rev))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(git_err_to_py_err)?;
+
+        refresh_index(&self.inner).map_err(git_err_to_py_err)?;
+        let (original_head, head_name) = head_position(&self.inner).map_err(git_err_to_py_err)?;
+        if let Some(onto) = onto {
+            switch_to(&self.inner, onto).map_err(git_err_to_py_err)?;
+        }
+        let base = self
+            .inner
+            .head()
+            .and_then(|head| head.peel_to_commit())
+            .map_err(git_err_to_py_err)?
+            .id();
+
+        let progress = CherryPickResult {
+            original_head: original_head.to_string(),
+            head_name: head_name.clone(),
+            base: base.to_string(),
+            applied: Vec::new(),
+            skipped: Vec::new(),
+            conflicts: Vec::new(),
+            current: None,
+            remaining: Vec::new(),
+            mainline,
+        };
+        let picked = pick_sequence(&self.inner, progress, &oids);
+        if picked.is_err() && onto.is_some() {
+            // Don't leave HEAD on `onto` when the sequence failed outright.
+            let _ = self.inner.cleanup_state();
+            let _ = restore_head(&self.inner, original_head, head_name.as_deref());
+        }
+        picked.map_err(git_err_to_py_err)
+    }
+
+    /// Commit the resolved pick that stopped `result` and pick the
+    /// remaining commits.
+    fn cherry_pick_continue(&self, result: &CherryPickResult) -> PyResult<CherryPickResult> {
+        let current = result
+            .current
+            .as_deref()
+            .ok_or_else(|| PyValueError::new_err("The cherry-pick did not stop on a conflict"))?;
+        refresh_index(&self.inner).map_err(git_err_to_py_err)?;
+        let index = self.inner.index().map_err(git_err_to_py_err)?;
+        if index.has_conflicts() {
+            return Err(PyValueError::new_err(
+                "Resolve all conflicts before continuing the cherry-pick",
+            ));
+        }
+
+        let picked = Oid::from_str(current)
+            .and_then(|oid| self.inner.find_commit(oid))
+            .map_err(git_err_to_py_err)?;
+        let mut progress = result.clone();
+        match commit_pick(&self.inner, &picked).map_err(git_err_to_py_err)? {
+            Some(new_id) => progress.applied.push(new_id.to_string()),
+            None => progress.skipped.push(current.to_string()),
+        }
+        let remaining = parse_oids(&result.remaining)?;
+        pick_sequence(&self.inner, progress, &remaining).map_err(git_err_to_py_err)
+    }
+
+    /// Abandon the cherry-pick that stopped `result`: the picked-onto branch
+    /// is reset to `result.base` and `HEAD` returns to the branch (or
+    /// detached commit) it was on before the cherry-pick.
+    fn cherry_pick_abort(&self, result: &CherryPickResult) -> PyResult<()> {
+        if result.current.is_none() {
+            return Err(PyValueError::new_err(
+                "The cherry-pick did not stop on a conflict",
+            ));
+        }
+        let base = Oid::from_str(&result.base)
+            .and_then(|oid| self.inner.find_object(oid, None))
+            .map_err(git_err_to_py_err)?;
+        self.inner
+            .reset(&base, ResetType::Hard, None)
+            .map_err(git_err_to_py_err)?;
+        self.inner.cleanup_state().map_err(git_err_to_py_err)?;
+        let original = Oid::from_str(&result.original_head).map_err(git_err_to_py_err)?;
+        restore_head(&self.inner, original, result.head_name.as_deref()).map_err(git_err_to_py_err)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum PlanAction {
+    Pick,
+    Reword,
+    Squash,
+    Fixup,
+}
+
+impl PlanAction {
+    fn parse(action: &str) -> PyResult<Option<Self>> {
+        match action {
+            "pick" => Ok(Some(PlanAction::Pick)),
+            "reword" => Ok(Some(PlanAction::Reword)),
+            "squash" => Ok(Some(PlanAction::Squash)),
+            "fixup" => Ok(Some(PlanAction::Fixup)),
+            "drop" => Ok(None),
+            other => Err(PyValueError::new_err(format!(
+                "Unknown rebase action '{}', expected 'pick', 'drop', 'reword', 'squash' or 'fixup'",
+                other
+            ))),
+        }
+    }
+}
+
+/// Commits between `upstream` and `HEAD` that a rebase would replay,
+/// oldest first, as chosen by libgit2's in-memory rebase (merge commits are
+/// left out).
+fn rebase_operations(repo: &Repository, upstream: Oid, onto: Oid) -> Result<Vec<Oid>, git2::Error> {
+    let upstream = repo.find_annotated_commit(upstream)?;
+    let onto = repo.find_annotated_commit(onto)?;
+    let mut options = RebaseOptions::new();
+    options.inmemory(true);
+    let mut rebase = repo.rebase(None, Some(&upstream), Some(&onto), Some(&mut options))?;
+    let ids = (0..rebase.len())
+        .filter_map(|i| rebase.nth(i).map(|operation| operation.id()))
+        .collect();
+    rebase.abort()?;
+    Ok(ids)
+}
+
+/// Check a plan's steps against the commits being rebased, dropping the
+/// `drop` steps.
+fn parse_plan(repo: &Repository, plan: &PyAny, commits: &[Oid]) -> PyResult<Vec<PlanStep>> {
+    let mut steps = Vec::new();
+    for item in plan.iter()? {
+        let item = item?;
+        let (action, rev, message): PlanStep = match item.extract::<(String, String)>() {
+            Ok((action, rev)) => (action, rev, None),
+            Err(_) => item.extract()?,
+        };
+        let Some(parsed) = PlanAction::parse(&action)? else {
+            continue;
+        };
+        let oid = resolve_commit(repo, &rev).map_err(git_err_to_py_err)?;
+        if !commits.contains(&oid) {
+            return Err(PyValueError::new_err(format!(
+                "'{}' is not one of the commits being rebased",
+                rev
+            )));
+        }
+        if parsed == PlanAction::Reword && message.is_none() {
+            return Err(PyValueError::new_err(format!(
+                "'reword' of '{}' needs a message",
+                rev
+            )));
+        }
+        if steps.is_empty() && matches!(parsed, PlanAction::Squash | PlanAction::Fixup) {
+            return Err(PyValueError::new_err(format!(
+                "Cannot '{}' '{}' without a previous commit",
+                action, rev
+            )));
+        }
+        steps.push((action, oid.to_string(), message));
+    }
+    Ok(steps)
+}
+
+/// Commit the merged `index` of `rebase` for `step` on top of
+/// `progress.new_head`.
+///
+/// `squash` and `fixup` replace the tip instead of adding to it; a pick
+/// that changes nothing is recorded as skipped.
+fn apply_step(
+    repo: &Repository,
+    progress: &mut RebaseResult,
+    step: &PlanStep,
+    rebase: &mut Rebase,
+    index: &mut Index,
+) -> Result<(), git2::Error> {
+    let (action, commit_id, message) = step;
+    let committer = repo.signature()?;
+
+    let new_id = match action.as_str() {
+        "squash" | "fixup" if progress.new_head != progress.onto => {
+            let picked = repo.find_commit(Oid::from_str(commit_id)?)?;
+            let tip = repo.find_commit(Oid::from_str(&progress.new_head)?)?;
+            let tree = repo.find_tree(index.write_tree_to(repo)?)?;
+            let message = match (action.as_str(), message) {
+                ("squash", Some(message)) => message.clone(),
+                ("squash", None) => format!(
+                    "{}\n\n{}",
+                    tip.message().unwrap_or("").trim_end(),
+                    picked.message().unwrap_or("")
+                ),
+                _ => tip.message().unwrap_or("").to_string(),
+            };
+            let parents: Vec<_> = tip.parents().collect();
+            let parents: Vec<_> = parents.iter().collect();
+            let new_id = repo.commit(None, &tip.author(), &committer, &message, &tree, &parents)?;
+            for target in progress.mapping.values_mut() {
+                if *target == progress.new_head {
+                    *target = new_id.to_string();
+                }
+            }
+            new_id
+        }
+        _ => {
+            // Without a message the commit keeps the picked one's, as it
+            // keeps its author.
+            let message = match action.as_str() {
+                "reword" => message.as_deref(),
+                _ => None,
+            };
+            match rebase.commit(None, &committer, message) {
+                Ok(new_id) => new_id,
+                Err(e) if e.code() == ErrorCode::Applied => {
+                    progress.skipped.push(commit_id.clone());
+                    return Ok(());
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    };
+    progress
+        .mapping
+        .insert(commit_id.clone(), new_id.to_string());
+    progress.new_head = new_id.to_string();
+    Ok(())
+}
+
+/// Start an in-memory rebase of `step`'s commit alone onto the current tip
+/// and merge it, returning the session and its index.
+fn merge_step<'r>(
+    repo: &'r Repository,
+    progress: &RebaseResult,
+    step: &PlanStep,
+) -> Result<(Rebase<'r>, Index), git2::Error> {
+    let picked = repo.find_commit(Oid::from_str(&step.1)?)?;
+    let branch = repo.find_annotated_commit(picked.id())?;
+    let upstream = match picked.parent_ids().next() {
+        Some(parent) => Some(repo.find_annotated_commit(parent)?),
+        None => None,
+    };
+    let onto = repo.find_annotated_commit(Oid::from_str(&progress.new_head)?)?;
+    let mut options = RebaseOptions::new();
+    options.inmemory(true);
+    let mut rebase = repo.rebase(
+        Some(&branch),
+        upstream.as_ref(),
+        Some(&onto),
+        Some(&mut options),
+    )?;
+    rebase
+        .next()
+        .ok_or_else(|| git2::Error::from_str("Nothing to rebase"))??;
+    let index = rebase.inmemory_index()?;
+    Ok((rebase, index))
+}
+
+/// Point the rebased branch (or detached `HEAD`) at the rewritten commits
+/// and check them out.
+fn finish_rebase(repo: &Repository, progress: &RebaseResult) -> Result<(), git2::Error> {
+    if progress.new_head == progress.original_head {
+        return Ok(());
+    }
+    let new_head = Oid::from_str(&progress.new_head)?;
+    let original_head = Oid::from_str(&progress.original_head)?;
+    let commit = repo.find_commit(new_head)?;
+    repo.checkout_tree(commit.as_object(), Some(CheckoutBuilder::new().safe()))?;
+    let message = format!(
+        "rebase (finish): {} onto {}",
+        progress.head_name.as_deref().unwrap_or("HEAD"),
+        progress.onto
+    );
+    let name = progress.head_name.as_deref().unwrap_or("HEAD");
+    repo.reference_matching(name, new_head, true, original_head, &message)?;
+    Ok(())
+}
+
+/// Apply `steps` in order, stopping at the first conflict. Once every
+/// step has applied the rebase is finished.
+///
+/// Each step runs in its own in-memory `git2::Rebase` of that one commit:
+/// libgit2 fixes a rebase's operations when it starts, so a single session
+/// could not reorder, squash or fix up commits, and an in-memory rebase
+/// cannot be reopened with `open_rebase` between the Python calls that stop
+/// on a conflict and continue it.
+fn run_rebase(
+    repo: &Repository,
+    mut progress: RebaseResult,
+    steps: &[PlanStep],
+) -> Result<RebaseResult, git2::Error> {
+    for (i, step) in steps.iter().enumerate() {
+        let (mut rebase, mut index) = merge_step(repo, &progress, step)?;
+        if index.has_conflicts() {
+            progress.conflicts = index_conflicts(&index)?;
+            progress.current = Some(step.clone());
+            progress.remaining = steps[i + 1..].to_vec();
+            return Ok(progress);
+        }
+        apply_step(repo, &mut progress, step, &mut rebase, &mut index)?;
+    }
+
+    progress.conflicts = Vec::new();
+    progress.current = None;
+    progress.remaining = Vec::new();
+    finish_rebase(repo, &progress)?;
+    Ok(progress)
+}
+
+/// The step `result` stopped on, or an error when it did not stop.
+fn stopped_step(result: &RebaseResult) -> PyResult<&PlanStep> {
+    result
+        .current
+        .as_ref()
+        .ok_or_else(|| PyValueError::new_err("The rebase did not stop on a conflict"))
+}
+
+#[pymethods]
+impl Repo {
+    /// Rebase the commits between `upstream` and `HEAD` onto `onto`
+    /// (default `upstream`), like `git rebase [-i]`.
+    ///
+    /// The commits are replayed in memory, so nothing changes until every
+    /// step has applied; the branch is then moved and checked out. Like git,
+    /// it refuses to start with uncommitted changes to tracked files.
+    /// `interactive_plan` is a list of `(action, commit)` or `(action,
+    /// commit, message)` tuples with the actions `pick`, `drop`, `reword`,
+    /// `squash` and `fixup`. Commits left out of the plan are dropped; by
+    /// default every commit is picked.
+    #[pyo3(signature = (upstream, onto=None, interactive_plan=None))]
+    fn rebase(
+        &self,
+        upstream: &str,
+        onto: Option<&str>,
+        interactive_plan: Option<&PyAny>,
+    ) -> PyResult<RebaseResult> {
+        let upstream = resolve_commit(&self.inner, upstream).map_err(git_err_to_py_err)?;
+        let onto = match onto {
+            Some(onto) => resolve_commit(&self.inner, onto).map_err(git_err_to_py_err)?,
+            None => upstream,
+        };
+        refresh_index(&self.inner).map_err(git_err_to_py_err)?;
+        let mut status = StatusOptions::new();
+        status.include_untracked(false).exclude_submodules(true);
+        let changes = self
+            .inner
+            .statuses(Some(&mut status))
+            .map_err(git_err_to_py_err)?;
+        if !changes.is_empty() {
+            return Err(PyValueError::new_err(
+                "Cannot rebase with uncommitted changes; commit or stash them first",
+            ));
+        }
+        let head = self.inner.head().map_err(git_err_to_py_err)?;
+        let original_head = head.peel_to_commit().map_err(git_err_to_py_err)?.id();
+        let head_name = if head.is_branch() {
+            head.name().map(str::to_string)
+        } else {
+            None
+        };
+
+        let commits = rebase_operations(&self.inner, upstream, onto).map_err(git_err_to_py_err)?;
+        let steps = match interactive_plan {
+            Some(plan) => parse_plan(&self.inner, plan, &commits)?,
+            None => commits
+                .iter()
+                .map(|oid| ("pick".to_string(), oid.to_string(), None))
+                .collect(),
+        };
+
+        let progress = RebaseResult {
+            original_head: original_head.to_string(),
+            head_name,
+            onto: onto.to_string(),
+            new_head: onto.to_string(),
+            mapping: HashMap::new(),
+            skipped: Vec::new(),
+            conflicts: Vec::new(),
+            current: None,
+            remaining: Vec::new(),
+        };
+        run_rebase(&self.inner, progress, &steps).map_err(git_err_to_py_err)
+    }
+
+    /// Apply the step that stopped `result` using the resolved contents in
+    /// `resolved` (path to bytes, or `None` to delete the path), then the
+    /// remaining steps.
+    #[pyo3(signature = (result, resolved=None))]
+    fn rebase_continue(
+        &self,
+        result: &RebaseResult,
+        resolved: Option<HashMap<String, Option<Vec<u8>>>>,
+    ) -> PyResult<RebaseResult> {
+        let step = stopped_step(result)?;
+        let (mut rebase, mut index) =
+            merge_step(&self.inner, result, step).map_err(git_err_to_py_err)?;
+        for (path, content) in resolved.unwrap_or_default() {
+            let mode = index
+                .conflict_get(Path::new(&path))
+                .ok()
+                .and_then(|conflict| conflict.our.or(conflict.their).or(conflict.ancestor))
+                .map(|entry| entry.mode)
+                .ok_or_else(|| {
+                    PyValueError::new_err(format!("'{}' is not conflicted in this step", path))
+                })?;
+            index
+                .conflict_remove(Path::new(&path))
+                .map_err(git_err_to_py_err)?;
+            if let Some(content) = content {
+                let id = self.inner.blob(&content).map_err(git_err_to_py_err)?;
+                index
+                    .add(&resolved_entry(&path, mode, id, content.len() as u32))
+                    .map_err(git_err_to_py_err)?;
+            }
+        }
+        if index.has_conflicts() {
+            let unresolved = index_conflicts(&index).map_err(git_err_to_py_err)?;
+            let paths: Vec<_> = unresolved.iter().map(|c| c.path.as_str()).collect();
+            return Err(PyValueError::new_err(format!(
+                "Unresolved conflicts remain in: {}",
+                paths.join(", ")
+            )));
+        }
+
+        let mut progress = result.clone();
+        apply_step(&self.inner, &mut progress, step, &mut rebase, &mut index)
+            .map_err(git_err_to_py_err)?;
+        run_rebase(&self.inner, progress, &result.remaining).map_err(git_err_to_py_err)
+    }
+
+    /// Leave out the step that stopped `result` and apply the remaining
+    /// steps.
+    fn rebase_skip(&self, result: &RebaseResult) -> PyResult<RebaseResult> {
+        stopped_step(result)?;
+        run_rebase(&self.inner, result.clone(), &result.remaining).map_err(git_err_to_py_err)
+    }
+
+    /// Abandon the rebase that stopped `result`, like `git rebase --abort`.
+    ///
+    /// Nothing is written until a rebase finishes, so the branch, index and
+    /// working tree are left exactly as they are; the commits replayed so
+    /// far are left unreferenced.
+    fn rebase_abort(&self, result: &RebaseResult) -> PyResult<()> {
+        stopped_step(result)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::index::logic::index_conflicts;
+    use crate::repo::Repo;
+    use crate::test_utils::TestRepo;
+    use git2::{Oid, RepositoryState};
+    use pyo3::prelude::*;
+    use pyo3::types::PyList;
+    use std::path::Path;
+
+    fn index_tree(t: &TestRepo) -> Oid {
+        t.git().index().unwrap().write_tree().unwrap()
+    }
+
+    fn tree_of(t: &TestRepo, commit: Oid) -> Oid {
+        t.git().find_commit(commit).unwrap().tree_id()
+    }
+
+    #[test]
+    fn reset_modes() {
+        let t = TestRepo::new();
+        let first = t.commit("file.txt", "one\n", "first");
+        let second = t.commit("file.txt", "two\n", "second");
+
+        t.repo.reset(&first.to_string(), "soft").unwrap();
+        assert_eq!(t.head(), first);
+        assert_eq!(index_tree(&t), tree_of(&t, second));
+        assert_eq!(t.read("file.txt"), "two\n");
+
+        t.repo.reset(&second.to_string(), "soft").unwrap();
+        t.repo.reset(&first.to_string(), "mixed").unwrap();
+        assert_eq!(index_tree(&t), tree_of(&t, first));
+        assert_eq!(t.read("file.txt"), "two\n");
+
+        t.repo.reset(&second.to_string(), "hard").unwrap();
+        assert_eq!(t.head(), second);
+        assert_eq!(t.read("file.txt"), "two\n");
+        t.repo.reset(&first.to_string(), "hard").unwrap();
+        assert_eq!(t.read("file.txt"), "one\n");
+        assert!(t.is_clean());
+
+        assert!(t.repo.reset("HEAD", "keep").is_err());
+    }
+
+    #[test]
+    fn revert_commits_the_inverse() {
+        let t = TestRepo::new();
+        let first = t.commit("file.txt", "one\n", "first");
+        let second = t.commit("file.txt", "two\n", "second");
+
+        let result = t.repo.revert(&second.to_string(), None, true).unwrap();
+        let new_id = result.commit_id.expect("revert should commit");
+        assert!(result.conflicts.is_empty());
+        assert_eq!(t.head().to_string(), new_id);
+        assert_eq!(tree_of(&t, t.head()), tree_of(&t, first));
+        let commit = t.git().find_commit(t.head()).unwrap();
+        assert_eq!(commit.summary(), Some("Revert \"second\""));
+        assert_eq!(commit.parent_id(0).unwrap(), second);
+        let reflog = t.git().reflog("HEAD").unwrap();
+        assert_eq!(
+            reflog.get(0).unwrap().message(),
+            Some("revert: Revert \"second\"")
+        );
+        assert_eq!(t.git().state(), RepositoryState::Clean);
+    }
+
+    #[test]
+    fn revert_without_commit_leaves_changes_staged() {
+        let t = TestRepo::new();
+        let first = t.commit("file.txt", "one\n", "first");
+        let second = t.commit("file.txt", "two\n", "second");
+
+        let result = t.repo.revert(&second.to_string(), None, false).unwrap();
+        assert_eq!(result.commit_id, None);
+        assert_eq!(t.head(), second);
+        assert_eq!(index_tree(&t), tree_of(&t, first));
+        assert_eq!(t.read("file.txt"), "one\n");
+    }
+
+    #[test]
+    fn revert_with_conflicts_stops() {
+        let t = TestRepo::new();
+        t.commit("file.txt", "one\n", "first");
+        let second = t.commit("file.txt", "two\n", "second");
+        let third = t.commit("file.txt", "three\n", "third");
+
+        let result = t.repo.revert(&second.to_string(), None, true).unwrap();
+        assert_eq!(result.commit_id, None);
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(result.conflicts[0].path, "file.txt");
+        assert_eq!(t.head(), third);
+        assert_eq!(t.git().state(), RepositoryState::Revert);
+    }
+
+    /// `base` on the default branch, then a `topic` branch adding
+    /// `other.txt` and changing `file.txt`, while the default branch changes
+    /// `file.txt` differently. Leaves the default branch checked out.
+    fn diverged() -> (TestRepo, String, Vec<Oid>) {
+        let t = TestRepo::new();
+        let base = t.commit("file.txt", "base\n", "base");
+        let main = t.head_name().unwrap();
+        t.checkout_new_branch("topic", base);
+        let topic = vec![
+            t.commit("other.txt", "other\n", "add other"),
+            t.commit("file.txt", "topic\n", "change file on topic"),
+        ];
+        t.checkout(&main);
+        t.commit("file.txt", "main\n", "change file on main");
+        (t, main, topic)
+    }
+
+    fn stage(t: &TestRepo, path: &str, content: &str) {
+        t.write(path, content);
+        let mut index = t.git().index().unwrap();
+        index.add_path(Path::new(path)).unwrap();
+        index.write().unwrap();
+    }
+
+    fn ids(py: Python<'_>, commits: &[Oid]) -> PyObject {
+        let ids: Vec<String> = commits.iter().map(Oid::to_string).collect();
+        PyList::new(py, ids).into()
+    }
+
+    #[test]
+    fn cherry_pick_applies_commits_in_order() {
+        let (t, _, topic) = diverged();
+        let start = t.head();
+        Python::with_gil(|py| {
+            let result = t
+                .repo
+                .cherry_pick(ids(py, &topic[..1]).as_ref(py), None, Some(1))
+                .unwrap();
+            assert!(result.conflicts.is_empty());
+            assert_eq!(result.original_head, start.to_string());
+            assert_eq!(result.base, start.to_string());
+            assert_eq!(result.applied, vec![t.head().to_string()]);
+        });
+        let picked = t.git().find_commit(t.head()).unwrap();
+        assert_eq!(picked.summary(), Some("add other"));
+        assert_eq!(picked.parent_id(0).unwrap(), start);
+        assert_eq!(t.read("other.txt"), "other\n");
+        let reflog = t.git().reflog("HEAD").unwrap();
+        assert_eq!(
+            reflog.get(0).unwrap().message(),
+            Some("cherry-pick: add other")
+        );
+    }
+
+    #[test]
+    fn cherry_pick_skips_changes_already_present() {
+        let (t, _, topic) = diverged();
+        Python::with_gil(|py| {
+            let first = ids(py, &topic[..1]);
+            t.repo.cherry_pick(first.as_ref(py), None, None).unwrap();
+            let head = t.head();
+            let result = t.repo.cherry_pick(first.as_ref(py), None, None).unwrap();
+            assert!(result.applied.is_empty());
+            assert_eq!(result.skipped, vec![topic[0].to_string()]);
+            assert_eq!(t.head(), head);
+        });
+    }
+
+    #[test]
+    fn cherry_pick_continue_after_resolving() {
+        let (t, _, topic) = diverged();
+        Python::with_gil(|py| {
+            let result = t
+                .repo
+                .cherry_pick(ids(py, &[topic[1], topic[0]]).as_ref(py), None, None)
+                .unwrap();
+            assert!(!result.conflicts.is_empty());
+            assert_eq!(result.current, Some(topic[1].to_string()));
+            assert_eq!(result.remaining, vec![topic[0].to_string()]);
+            assert!(t.repo.cherry_pick_continue(&result).is_err());
+
+            stage(&t, "file.txt", "resolved\n");
+            let result = t.repo.cherry_pick_continue(&result).unwrap();
+            assert!(result.conflicts.is_empty());
+            assert_eq!(result.applied.len(), 2);
+            assert_eq!(result.applied[1], t.head().to_string());
+        });
+        assert_eq!(t.read("file.txt"), "resolved\n");
+        assert_eq!(t.read("other.txt"), "other\n");
+        assert_eq!(t.git().state(), RepositoryState::Clean);
+        let resolved = t.git().find_commit(t.head()).unwrap().parent(0).unwrap();
+        assert_eq!(resolved.summary(), Some("change file on topic"));
+    }
+
+    #[test]
+    fn cherry_pick_abort_returns_to_the_original_branch() {
+        let (t, main, topic) = diverged();
+        let main_head = t.head();
+
+        Python::with_gil(|py| {
+            let main_tip = ids(py, &[main_head]);
+            let result = t
+                .repo
+                .cherry_pick(main_tip.as_ref(py), Some("topic"), None)
+                .unwrap();
+            assert!(!result.conflicts.is_empty());
+            assert_eq!(result.original_head, main_head.to_string());
+            assert_eq!(result.head_name.as_deref(), Some(main.as_str()));
+            assert_eq!(result.base, topic[1].to_string());
+            assert_eq!(t.head_name().as_deref(), Some("refs/heads/topic"));
+
+            t.repo.cherry_pick_abort(&result).unwrap();
+        });
+        assert_eq!(t.head_name(), Some(main));
+        assert_eq!(t.head(), main_head);
+        assert_eq!(t.read("file.txt"), "main\n");
+        assert!(t.is_clean());
+        assert_eq!(t.git().state(), RepositoryState::Clean);
+        assert_eq!(t.git().refname_to_id("refs/heads/topic").unwrap(), topic[1]);
+    }
+
+    #[test]
+    fn cherry_pick_failure_returns_to_the_original_branch() {
+        let (t, main, topic) = diverged();
+        let main_head = t.head();
+        let git = t.git();
+        let signature = git.signature().unwrap();
+        let tree = git.find_commit(main_head).unwrap().tree().unwrap();
+        let parents = [
+            git.find_commit(main_head).unwrap(),
+            git.find_commit(topic[1]).unwrap(),
+        ];
+        let merge = git
+            .commit(
+                None,
+                &signature,
+                &signature,
+                "merge",
+                &tree,
+                &[&parents[0], &parents[1]],
+            )
+            .unwrap();
+
+        Python::with_gil(|py| {
+            // A merge can't be picked without a mainline.
+            let picked = t
+                .repo
+                .cherry_pick(ids(py, &[merge]).as_ref(py), Some("topic"), None);
+            assert!(picked.is_err());
+        });
+        assert_eq!(t.head_name(), Some(main));
+        assert_eq!(t.head(), main_head);
+        assert_eq!(t.read("file.txt"), "main\n");
+        assert_eq!(t.git().state(), RepositoryState::Clean);
+    }
+
+    #[test]
+    fn cherry_pick_abort_needs_a_stopped_pick() {
+        let (t, _, topic) = diverged();
+        let result = Python::with_gil(|py| {
+            t.repo
+                .cherry_pick(ids(py, &topic[..1]).as_ref(py), None, None)
+                .unwrap()
+        });
+        let head = t.head();
+
+        let err = t.repo.cherry_pick_abort(&result).unwrap_err();
+        Python::with_gil(|py| assert!(err.is_instance_of::<pyo3::exceptions::PyValueError>(py)));
+        assert_eq!(t.head(), head);
+        assert_eq!(t.read("other.txt"), "other\n");
+    }
+
+    fn plan(py: Python<'_>, steps: &[(&str, Oid, Option<&str>)]) -> PyObject {
+        let steps: Vec<PyObject> = steps
+            .iter()
+            .map(|(action, id, message)| (*action, id.to_string(), *message).into_py(py))
+            .collect();
+        PyList::new(py, steps).into()
+    }
+
+    #[test]
+    fn rebase_with_plan_rewrites_the_branch() {
+        let t = TestRepo::new();
+        let base = t.commit("file.txt", "base\n", "base");
+        let a = t.commit("a.txt", "a\n", "add a");
+        let b = t.commit("b.txt", "b\n", "add b");
+        let c = t.commit("c.txt", "c\n", "add c");
+        let d = t.commit("d.txt", "d\n", "add d");
+        let branch = t.head_name();
+
+        let result = Python::with_gil(|py| {
+            let steps = plan(
+                py,
+                &[
+                    ("pick", a, None),
+                    ("fixup", b, None),
+                    ("drop", c, None),
+                    ("reword", d, Some("add d, reworded")),
+                ],
+            );
+            t.repo
+                .rebase(&base.to_string(), None, Some(steps.as_ref(py)))
+                .unwrap()
+        });
+        assert!(result.current.is_none());
+        assert_eq!(result.head_name, branch);
+        assert_eq!(t.head_name(), branch);
+        assert_eq!(t.head().to_string(), result.new_head);
+
+        let tip = t.git().find_commit(t.head()).unwrap();
+        assert_eq!(tip.summary(), Some("add d, reworded"));
+        let squashed = tip.parent(0).unwrap();
+        assert_eq!(squashed.summary(), Some("add a"));
+        assert_eq!(squashed.parent_id(0).unwrap(), base);
+        assert_eq!(result.mapping[&a.to_string()], squashed.id().to_string());
+        assert_eq!(result.mapping[&b.to_string()], squashed.id().to_string());
+        assert!(!result.mapping.contains_key(&c.to_string()));
+        assert!(!t.path().join("c.txt").exists());
+        assert_eq!(t.read("b.txt"), "b\n");
+        assert!(t.is_clean());
+    }
+
+    #[test]
+    fn rebase_skips_commits_already_upstream() {
+        let t = TestRepo::new();
+        let base = t.commit("file.txt", "base\n", "base");
+        let main = t.head_name().unwrap();
+        t.checkout_new_branch("topic", base);
+        let same = t.commit("a.txt", "a\n", "add a on topic");
+        let b = t.commit("b.txt", "b\n", "add b");
+        t.checkout(&main);
+        let upstream = t.commit("a.txt", "a\n", "add a on main");
+        t.checkout("refs/heads/topic");
+
+        let result = t.repo.rebase(&main, None, None).unwrap();
+        assert!(result.current.is_none());
+        assert_eq!(result.skipped, vec![same.to_string()]);
+        let tip = t.git().find_commit(t.head()).unwrap();
+        assert_eq!(result.mapping[&b.to_string()], tip.id().to_string());
+        assert_eq!(tip.summary(), Some("add b"));
+        assert_eq!(tip.author().email(), Some("test@example.com"));
+        assert_eq!(tip.parent_id(0).unwrap(), upstream);
+    }
+
+    #[test]
+    fn rebase_continue_applies_the_resolution() {
+        let (t, main, topic) = diverged();
+        let main_head = t.head();
+        t.checkout("refs/heads/topic");
+
+        let result = t.repo.rebase(&main, None, None).unwrap();
+        assert_eq!(
+            result.current.as_ref().map(|step| step.1.clone()),
+            Some(topic[1].to_string())
+        );
+        assert_eq!(result.conflicts[0].path, "file.txt");
+        assert_eq!(t.head(), topic[1]);
+        assert!(t.repo.rebase_continue(&result, None).is_err());
+
+        let resolved = [("file.txt".to_string(), Some(b"resolved\n".to_vec()))];
+        let result = t
+            .repo
+            .rebase_continue(&result, Some(resolved.into_iter().collect()))
+            .unwrap();
+        assert!(result.current.is_none());
+        assert_eq!(t.head_name().as_deref(), Some("refs/heads/topic"));
+        assert_eq!(t.head().to_string(), result.new_head);
+        assert_eq!(t.read("file.txt"), "resolved\n");
+        assert_eq!(t.read("other.txt"), "other\n");
+        let first = t.git().find_commit(t.head()).unwrap().parent(0).unwrap();
+        assert_eq!(first.parent_id(0).unwrap(), main_head);
+        let reflog = t.git().reflog("refs/heads/topic").unwrap();
+        assert_eq!(
+            reflog.get(0).unwrap().message(),
+            Some(format!("rebase (finish): refs/heads/topic onto {}", main_head).as_str())
+        );
+    }
+
+    #[test]
+    fn rebase_skip_leaves_out_the_conflicting_commit() {
+        let (t, main, topic) = diverged();
+        t.checkout("refs/heads/topic");
+
+        let result = t.repo.rebase(&main, None, None).unwrap();
+        let result = t.repo.rebase_skip(&result).unwrap();
+        assert!(result.current.is_none());
+        assert!(!result.mapping.contains_key(&topic[1].to_string()));
+        assert_eq!(t.read("file.txt"), "main\n");
+        assert_eq!(t.read("other.txt"), "other\n");
+        assert!(t.repo.rebase_skip(&result).is_err());
+    }
+
+    #[test]
+    fn rebase_abort_leaves_the_branch_and_work_alone() {
+        let (t, main, topic) = diverged();
+        t.checkout("refs/heads/topic");
+
+        let result = t.repo.rebase(&main, None, None).unwrap();
+        assert!(result.current.is_some());
+        assert_eq!(t.head(), topic[1]);
+        let later = t.commit("later.txt", "later\n", "commit after the stop");
+        t.write("file.txt", "uncommitted\n");
+
+        t.repo.rebase_abort(&result).unwrap();
+        assert_eq!(t.head_name().as_deref(), Some("refs/heads/topic"));
+        assert_eq!(t.head(), later);
+        assert_eq!(t.read("file.txt"), "uncommitted\n");
+        assert_eq!(t.read("later.txt"), "later\n");
+        assert_eq!(t.git().state(), RepositoryState::Clean);
+
+        // The uncommitted change is still there to block a new rebase.
+        assert!(t.repo.rebase(&main, None, None).is_err());
+        assert!(t.repo.rebase_abort(&result).is_ok());
+    }
+
+    #[test]
+    fn rebase_refuses_uncommitted_changes() {
+        let (t, main, topic) = diverged();
+        t.checkout("refs/heads/topic");
+        t.write("other.txt", "edited\n");
+
+        let err = t.repo.rebase(&main, None, None).unwrap_err();
+        Python::with_gil(|py| assert!(err.is_instance_of::<pyo3::exceptions::PyValueError>(py)));
+        assert_eq!(t.head(), topic[1]);
+        assert_eq!(t.read("other.txt"), "edited\n");
+
+        stage(&t, "other.txt", "staged\n");
+        assert!(t.repo.rebase(&main, None, None).is_err());
+
+        t.write("untracked.txt", "untracked\n");
+        stage(&t, "other.txt", "other\n");
+        let result = t.repo.rebase(&main, None, None).unwrap();
+        assert!(result.current.is_some());
+    }
+
+    #[test]
+    fn merge_commits_of_diverged_branches_reports_conflicts() {
+        let (t, main, topic) = diverged();
+        let ours = t.head();
+
+        Python::with_gil(|py| {
+            let repo = t.py_repo(py);
+            let merged = Repo::merge_commits(repo.borrow(py), &main, "topic").unwrap();
+            assert_eq!(merged.parents, vec![ours.to_string(), topic[1].to_string()]);
+            assert!(merged.inner.has_conflicts());
+            let conflicts = index_conflicts(&merged.inner).unwrap();
+            assert_eq!(conflicts.len(), 1);
+            let conflict = &conflicts[0];
+            assert_eq!(conflict.path, "file.txt");
+            assert_eq!(conflict.ancestor_path.as_deref(), Some("file.txt"));
+            let blob = |content: &str| t.git().blob(content.as_bytes()).unwrap().to_string();
+            assert_eq!(conflict.ancestor_id, Some(blob("base\n")));
+            assert_eq!(conflict.our_id, Some(blob("main\n")));
+            assert_eq!(conflict.their_id, Some(blob("topic\n")));
+        });
+        // Nothing on disk moved.
+        assert_eq!(t.head(), ours);
+        assert_eq!(t.read("file.txt"), "main\n");
+        assert!(!t.path().join("other.txt").exists());
+        assert!(t.is_clean());
+    }
+
+    #[test]
+    fn merge_commits_cleanly_writes_the_merged_tree() {
+        let t = TestRepo::new();
+        let base = t.commit("file.txt", "base\n", "base");
+        let main = t.head_name().unwrap();
+        t.checkout_new_branch("topic", base);
+        t.commit("topic.txt", "topic\n", "add topic");
+        t.checkout(&main);
+        t.commit("main.txt", "main\n", "add main");
+
+        Python::with_gil(|py| {
+            let repo = t.py_repo(py);
+            let mut merged = Repo::merge_commits(repo.borrow(py), "HEAD", "topic").unwrap();
+            assert!(!merged.inner.has_conflicts());
+            let tree_id = merged.inner.write_tree_to(t.git()).unwrap();
+            let tree = t.git().find_tree(tree_id).unwrap();
+            let names: Vec<_> = tree
+                .iter()
+                .map(|entry| entry.name().unwrap().to_string())
+                .collect();
+            assert_eq!(names, vec!["file.txt", "main.txt", "topic.txt"]);
+        });
+        assert!(!t.path().join("topic.txt").exists());
+    }
+
+    #[test]
+    fn merge_trees_uses_the_given_or_an_empty_ancestor() {
+        let (t, main, _) = diverged();
+        let base = t.git().find_commit(t.head()).unwrap().parent_id(0).unwrap();
+
+        Python::with_gil(|py| {
+            let repo = t.py_repo(py);
+            let base = base.to_string();
+            let merged = Repo::merge_trees(repo.borrow(py), Some(&base), &main, "topic").unwrap();
+            assert!(merged.parents.is_empty());
+            let conflicts = index_conflicts(&merged.inner).unwrap();
+            assert_eq!(conflicts.len(), 1);
+            assert!(conflicts[0].ancestor_id.is_some());
+
+            // Without an ancestor both sides added file.txt.
+            let merged = Repo::merge_trees(repo.borrow(py), None, &main, "topic").unwrap();
+            let conflicts = index_conflicts(&merged.inner).unwrap();
+            assert_eq!(conflicts.len(), 1);
+            assert_eq!(conflicts[0].ancestor_id, None);
+            assert!(merged.inner.get_path(Path::new("other.txt"), 0).is_some());
+
+            // A tree merged with itself is clean.
+            let merged = Repo::merge_trees(repo.borrow(py), Some(&base), "topic", "topic").unwrap();
+            assert!(!merged.inner.has_conflicts());
+
+            let err = Repo::merge_trees(repo.borrow(py), None, "missing", "topic");
+            assert!(err.is_err());
+        });
+    }
+}
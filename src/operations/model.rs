@@ -0,0 +1,137 @@
+use crate::index::Conflict;
+use crate::utils::py_model;
+use pyo3::prelude::*;
+use std::collections::HashMap;
+
+/// Outcome of `Repo.revert`: the new commit, or the conflicts that stopped it.
+#[pyclass(module = "rustygit")]
+#[derive(Clone, Debug)]
+pub struct RevertResult {
+    /// Id of the revert commit; `None` when not committed or conflicted
+    #[pyo3(get)]
+    pub commit_id: Option<String>,
+    #[pyo3(get)]
+    pub conflicts: Vec<Conflict>,
+}
+
+py_model!(
+    RevertResult,
+    fields: [commit_id, conflicts],
+    key: [commit_id],
+    repr: [commit_id, conflicts],
+);
+
+#[pymethods]
+impl RevertResult {
+    /// Whether the revert stopped with conflicts in the index
+    #[getter]
+    fn has_conflicts(&self) -> bool {
+        !self.conflicts.is_empty()
+    }
+}
+
+/// Progress of `Repo.cherry_pick`. When `current` is set the sequence
+/// stopped on conflicts; pass the result to `Repo.cherry_pick_continue` once
+/// they are resolved, or to `Repo.cherry_pick_abort`.
+#[pyclass(module = "rustygit")]
+#[derive(Clone, Debug)]
+pub struct CherryPickResult {
+    /// `HEAD` before the cherry-pick, i.e. before switching to `onto`
+    #[pyo3(get)]
+    pub original_head: String,
+    /// Branch `HEAD` was on before the cherry-pick; `None` when detached
+    #[pyo3(get)]
+    pub head_name: Option<String>,
+    /// Commit the first pick was applied on top of
+    #[pyo3(get)]
+    pub base: String,
+    /// Ids of the new commits, in order
+    #[pyo3(get)]
+    pub applied: Vec<String>,
+    /// Commits skipped because their changes were already present
+    #[pyo3(get)]
+    pub skipped: Vec<String>,
+    #[pyo3(get)]
+    pub conflicts: Vec<Conflict>,
+    /// The commit whose pick stopped with conflicts
+    #[pyo3(get)]
+    pub current: Option<String>,
+    /// Commits still to pick after `current`
+    #[pyo3(get)]
+    pub remaining: Vec<String>,
+    #[pyo3(get)]
+    pub mainline: Option<u32>,
+}
+
+py_model!(
+    CherryPickResult,
+    fields: [original_head, head_name, base, applied, skipped, conflicts, current, remaining, mainline],
+    key: [original_head, current],
+    repr: [applied, current, remaining],
+);
+
+#[pymethods]
+impl CherryPickResult {
+    /// Whether the sequence stopped with conflicts in the index
+    #[getter]
+    fn has_conflicts(&self) -> bool {
+        !self.conflicts.is_empty()
+    }
+}
+
+/// One step of a rebase plan: `(action, commit_id, message)`, where
+/// `message` is only set for `reword` and `squash`.
+pub type PlanStep = (String, String, Option<String>);
+
+/// Progress of `Repo.rebase`. The rebase happens in memory: refs and the
+/// working tree are only updated once every step has applied. When
+/// `current` is set the rebase stopped on conflicts; pass the result to
+/// `Repo.rebase_continue` with the resolved contents, `Repo.rebase_skip`
+/// or `Repo.rebase_abort`.
+#[pyclass(module = "rustygit")]
+#[derive(Clone, Debug)]
+pub struct RebaseResult {
+    /// `HEAD` before the rebase started
+    #[pyo3(get)]
+    pub original_head: String,
+    /// Branch being rebased; `None` when `HEAD` was detached
+    #[pyo3(get)]
+    pub head_name: Option<String>,
+    /// Commit the rewritten commits are replayed onto
+    #[pyo3(get)]
+    pub onto: String,
+    /// Tip of the rewritten commits so far
+    #[pyo3(get)]
+    pub new_head: String,
+    /// Old commit id to rewritten commit id; squashed commits map to the
+    /// commit they were folded into
+    #[pyo3(get)]
+    pub mapping: HashMap<String, String>,
+    /// Commits left out because their changes were already present
+    #[pyo3(get)]
+    pub skipped: Vec<String>,
+    #[pyo3(get)]
+    pub conflicts: Vec<Conflict>,
+    /// The step that stopped with conflicts
+    #[pyo3(get)]
+    pub current: Option<PlanStep>,
+    /// Steps still to apply after `current`
+    #[pyo3(get)]
+    pub remaining: Vec<PlanStep>,
+}
+
+py_model!(
+    RebaseResult,
+    fields: [original_head, head_name, onto, new_head, mapping, skipped, conflicts, current, remaining],
+    key: [original_head, onto, new_head, current],
+    repr: [head_name, new_head, current, remaining],
+);
+
+#[pymethods]
+impl RebaseResult {
+    /// Whether the rebase stopped with conflicts
+    #[getter]
+    fn has_conflicts(&self) -> bool {
+        !self.conflicts.is_empty()
+    }
+}
@@ -0,0 +1,252 @@
+use crate::commits::{BlameError, BlameErrorKind};
+use crate::utils::{git_err_to_py_err, load_mailmap, mailmapped_identity};
+use git2::{
+    BlameOptions, Mailmap, ObjectType, Oid, Pathspec, PathspecFlags, Repository, TreeWalkMode,
+    TreeWalkResult,
+};
+use pyo3::prelude::*;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::model::{OwnershipResult, OwnershipRow};
+
+/// Lines owned by a single (mailmapped) identity within one file.
+#[derive(Clone, Debug)]
+struct AuthorLines {
+    author: String,
+    author_email: String,
+    lines: usize,
+    last_touched: i64,
+}
+
+/// Blame every file matching `pathspec` (and not matching `exclude`) at `rev`
+/// and report how many surviving lines each author owns per file, per
+/// directory and for the whole tree.
+///
+/// Authors are canonicalized through the supplied `mailmap` text, or the
+/// repository's own mailmap when none is given. Rows are dictionaries so
+/// they can be passed straight to `pandas.DataFrame`. Files that cannot be
+/// blamed are left out of the rows and reported in `errors`.
+#[pyfunction]
+#[pyo3(signature = (repo, rev="HEAD", pathspec=None, exclude=None, mailmap=None))]
+pub fn code_ownership(
+    py: Python<'_>,
+    repo: &str,
+    rev: &str,
+    pathspec: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
+    mailmap: Option<&str>,
+) -> PyResult<OwnershipResult> {
+    let (rows, errors) = py.allow_threads(|| {
+        compute_ownership(
+            repo,
+            rev,
+            &pathspec.unwrap_or_default(),
+            &exclude.unwrap_or_default(),
+            mailmap,
+        )
+    })?;
+
+    let rows = rows
+        .iter()
+        .map(|row| row.to_py_dict(py).map(|d| d.to_object(py)))
+        .collect::<PyResult<_>>()?;
+    Ok(OwnershipResult { rows, errors })
+}
+
+pub fn compute_ownership(
+    repo_path: &str,
+    rev: &str,
+    pathspec: &[String],
+    exclude: &[String],
+    mailmap: Option<&str>,
+) -> PyResult<(Vec<OwnershipRow>, Vec<BlameError>)> {
+    let repo = Repository::open(repo_path).map_err(git_err_to_py_err)?;
+    // Surface a malformed mailmap once instead of from every worker.
+    load_mailmap(&repo, mailmap).map_err(git_err_to_py_err)?;
+    let commit = repo
+        .revparse_single(rev)
+        .and_then(|obj| obj.peel_to_commit())
+        .map_err(git_err_to_py_err)?;
+    let files = matching_files(
+        &repo,
+        &commit.tree().map_err(git_err_to_py_err)?,
+        pathspec,
+        exclude,
+    )?;
+    let commit_id = commit.id();
+
+    let outcomes: Vec<_> = files
+        .par_iter()
+        .map_init(
+            || {
+                let repo = Repository::open(repo_path)?;
+                let mailmap = load_mailmap(&repo, mailmap)?;
+                Ok::<_, git2::Error>((repo, mailmap))
+            },
+            |state, path| match state {
+                Ok((repo, mailmap)) => (path, blame_authors(repo, mailmap, commit_id, path)),
+                Err(e) => (
+                    path,
+                    Err(git2::Error::new(e.code(), e.class(), e.message())),
+                ),
+            },
+        )
+        .collect();
+
+    let mut per_file = Vec::new();
+    let mut errors = Vec::new();
+    for (path, authors) in outcomes {
+        match authors {
+            Ok(authors) => per_file.push((path.clone(), authors)),
+            Err(e) => errors.push(BlameError::new(
+                path.clone(),
+                BlameErrorKind::Git,
+                format!("Failed to blame file: {}", e),
+            )),
+        }
+    }
+
+    Ok((aggregate(per_file), errors))
+}
+
+/// List blob paths in `tree` selected by `pathspec` and not by `exclude`.
+fn matching_files(
+    repo: &Repository,
+    tree: &git2::Tree,
+    pathspec: &[String],
+    exclude: &[String],
+) -> PyResult<Vec<String>> {
+    let include = Pathspec::new(pathspec.iter()).map_err(git_err_to_py_err)?;
+    let exclude = if exclude.is_empty() {
+        None
+    } else {
+        Some(Pathspec::new(exclude.iter()).map_err(git_err_to_py_err)?)
+    };
+
+    let mut files = Vec::new();
+    tree.walk(TreeWalkMode::PreOrder, |root, entry| {
+        if entry.kind() != Some(ObjectType::Blob) {
+            return TreeWalkResult::Ok;
+        }
+        let path = format!("{}{}", root, entry.name().unwrap_or(""));
+        let matches = include.matches_path(Path::new(&path), PathspecFlags::DEFAULT)
+            && !exclude
+                .as_ref()
+                .is_some_and(|ex| ex.matches_path(Path::new(&path), PathspecFlags::DEFAULT));
+        if matches {
+            let is_binary = repo
+                .find_blob(entry.id())
+                .map(|blob| blob.is_binary())
+                .unwrap_or(true);
+            if !is_binary {
+                files.push(path);
+            }
+        }
+        TreeWalkResult::Ok
+    })
+    .map_err(git_err_to_py_err)?;
+
+    Ok(files)
+}
+
+/// Blame `path` as of `commit_id` and count surviving lines per identity.
+fn blame_authors(
+    repo: &Repository,
+    mailmap: &Mailmap,
+    commit_id: Oid,
+    path: &str,
+) -> Result<Vec<AuthorLines>, git2::Error> {
+    let mut options = BlameOptions::new();
+    options.newest_commit(commit_id);
+    let blame = repo.blame_file(Path::new(path), Some(&mut options))?;
+
+    let mut by_author: HashMap<(String, String), AuthorLines> = HashMap::new();
+    for hunk in blame.iter() {
+        let sig = hunk.final_signature();
+        let (author, author_email) = mailmapped_identity(mailmap, &sig);
+        let when = sig.when().seconds();
+
+        let entry = by_author
+            .entry((author.clone(), author_email.clone()))
+            .or_insert(AuthorLines {
+                author,
+                author_email,
+                lines: 0,
+                last_touched: when,
+            });
+        entry.lines += hunk.lines_in_hunk();
+        entry.last_touched = entry.last_touched.max(when);
+    }
+
+    Ok(by_author.into_values().collect())
+}
+
+/// Roll per-file author counts up into file, directory and total rows.
+fn aggregate(per_file: Vec<(String, Vec<AuthorLines>)>) -> Vec<OwnershipRow> {
+    type Scope = (&'static str, String);
+    let mut scopes: HashMap<Scope, HashMap<(String, String), AuthorLines>> = HashMap::new();
+
+    for (path, authors) in per_file {
+        let mut targets: Vec<Scope> = vec![("total", String::new()), ("file", path.clone())];
+        let mut dir = Path::new(&path).parent();
+        while let Some(d) = dir.filter(|d| !d.as_os_str().is_empty()) {
+            targets.push(("directory", d.to_string_lossy().replace('\\', "/")));
+            dir = d.parent();
+        }
+
+        for target in targets {
+            let scope = scopes.entry(target).or_default();
+            for a in &authors {
+                let entry = scope
+                    .entry((a.author.clone(), a.author_email.clone()))
+                    .or_insert(AuthorLines {
+                        lines: 0,
+                        ..a.clone()
+                    });
+                entry.lines += a.lines;
+                entry.last_touched = entry.last_touched.max(a.last_touched);
+            }
+        }
+    }
+
+    let mut rows = Vec::new();
+    for ((scope, path), authors) in scopes {
+        let total_lines: usize = authors.values().map(|a| a.lines).sum();
+        for a in authors.into_values() {
+            let percentage = if total_lines == 0 {
+                0.0
+            } else {
+                a.lines as f64 * 100.0 / total_lines as f64
+            };
+            rows.push(OwnershipRow {
+                scope,
+                path: path.clone(),
+                author: a.author,
+                author_email: a.author_email,
+                lines: a.lines,
+                total_lines,
+                percentage,
+                last_touched: a.last_touched,
+            });
+        }
+    }
+
+    rows.sort_by(|a, b| {
+        scope_rank(a.scope)
+            .cmp(&scope_rank(b.scope))
+            .then_with(|| a.path.cmp(&b.path))
+            .then_with(|| b.lines.cmp(&a.lines))
+            .then_with(|| a.author.cmp(&b.author))
+    });
+    rows
+}
+
+fn scope_rank(scope: &str) -> u8 {
+    match scope {
+        "total" => 0,
+        "directory" => 1,
+        _ => 2,
+    }
+}
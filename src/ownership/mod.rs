@@ -0,0 +1,6 @@
+pub mod logic;
+pub mod model;
+
+pub use logic::code_ownership;
+
+pub use model::OwnershipResult;
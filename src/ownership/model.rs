@@ -0,0 +1,58 @@
+use crate::commits::BlameError;
+use crate::utils::py_model;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+/// Aggregated ownership of one author within one scope (file, directory or total).
+#[derive(Clone, Debug)]
+pub struct OwnershipRow {
+    pub scope: &'static str,
+    pub path: String,
+    pub author: String,
+    pub author_email: String,
+    pub lines: usize,
+    pub total_lines: usize,
+    pub percentage: f64,
+    pub last_touched: i64,
+}
+
+impl OwnershipRow {
+    pub fn to_py_dict<'py>(&self, py: Python<'py>) -> PyResult<&'py PyDict> {
+        let dict = PyDict::new(py);
+        dict.set_item("scope", self.scope)?;
+        dict.set_item("path", &self.path)?;
+        dict.set_item("author", &self.author)?;
+        dict.set_item("author_email", &self.author_email)?;
+        dict.set_item("lines", self.lines)?;
+        dict.set_item("total_lines", self.total_lines)?;
+        dict.set_item("percentage", self.percentage)?;
+        dict.set_item("last_touched", self.last_touched)?;
+        Ok(dict)
+    }
+}
+
+/// Outcome of `code_ownership`: the ownership rows and the files that could
+/// not be blamed.
+#[pyclass(module = "rustygit")]
+#[derive(Clone, Debug)]
+pub struct OwnershipResult {
+    /// One dictionary per author per scope, ready for `pandas.DataFrame`
+    #[pyo3(get)]
+    pub rows: Vec<PyObject>,
+    /// One entry per file left out of `rows`
+    #[pyo3(get)]
+    pub errors: Vec<BlameError>,
+}
+
+py_model!(
+    OwnershipResult,
+    fields: [rows, errors],
+    repr: [errors],
+);
+
+#[pymethods]
+impl OwnershipResult {
+    fn __len__(&self) -> usize {
+        self.rows.len()
+    }
+}
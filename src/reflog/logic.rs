@@ -0,0 +1,300 @@
+use crate::commits::{Commit, Identity};
+use crate::repo::Repo;
+use crate::utils::{git_err_to_py_err, load_mailmap, resolve_commit};
+use git2::{Oid, Repository, Sort};
+use pyo3::prelude::*;
+use std::collections::HashSet;
+
+use super::model::ReflogEntry;
+
+/// Full name of `name` for reflog lookups: `HEAD` and `refs/...` are used
+/// as given, anything else is resolved like `git rev-parse` does (`main`
+/// becomes `refs/heads/main`).
+fn full_ref_name(repo: &Repository, name: &str) -> Result<String, git2::Error> {
+    if name == "HEAD" || name.starts_with("refs/") {
+        return Ok(name.to_string());
+    }
+    let reference = repo.resolve_reference_from_short_name(name)?;
+    Ok(reference.name().unwrap_or(name).to_string())
+}
+
+/// Commits that reflogs point at but no ref reaches, newest first: the
+/// work lost by resets, amends, rebases and deleted branches.
+///
+/// The stash reflog is left out, and its entries count as reachable, since
+/// stashes are not lost.
+fn unreachable_from_reflogs(repo: &Repository) -> Result<Vec<Oid>, git2::Error> {
+    let mut names = vec!["HEAD".to_string()];
+    for reference in repo.references()? {
+        if let Some(name) = reference?.name() {
+            names.push(name.to_string());
+        }
+    }
+
+    let mut walk = repo.revwalk()?;
+    walk.set_sorting(Sort::TIME)?;
+    for name in &names {
+        let is_stash = name == "refs/stash";
+        if let Ok(target) = repo.revparse_single(name).and_then(|o| o.peel_to_commit()) {
+            walk.hide(target.id())?;
+        }
+        for entry in repo.reflog(name)?.iter() {
+            for oid in [entry.id_old(), entry.id_new()] {
+                if oid.is_zero() || repo.find_commit(oid).is_err() {
+                    continue;
+                }
+                if is_stash {
+                    walk.hide(oid)?;
+                } else {
+                    walk.push(oid)?;
+                }
+            }
+        }
+    }
+    walk.collect()
+}
+
+fn to_commits(repo: &Repository, oids: &[Oid], mailmap: Option<&str>) -> PyResult<Vec<Commit>> {
+    let mailmap = load_mailmap(repo, mailmap).map_err(git_err_to_py_err)?;
+    oids.iter()
+        .map(|oid| {
+            let commit = repo.find_commit(*oid).map_err(git_err_to_py_err)?;
+            Ok(Commit::from_git_commit(&commit, Some(&mailmap)))
+        })
+        .collect()
+}
+
+#[pymethods]
+impl Repo {
+    /// The reflog of `ref`, most recent entry first, like `git reflog show`.
+    /// A ref without a reflog has no entries.
+    #[pyo3(signature = (r#ref="HEAD"))]
+    fn reflog(&self, r#ref: &str) -> PyResult<Vec<ReflogEntry>> {
+        let name = full_ref_name(&self.inner, r#ref).map_err(git_err_to_py_err)?;
+        let reflog = self.inner.reflog(&name).map_err(git_err_to_py_err)?;
+        Ok(reflog
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let committer = entry.committer();
+                ReflogEntry {
+                    index,
+                    old_id: entry.id_old().to_string(),
+                    new_id: entry.id_new().to_string(),
+                    committer: Identity {
+                        name: committer.name().unwrap_or("").to_string(),
+                        email: committer.email().unwrap_or("").to_string(),
+                    },
+                    time: committer.when().seconds(),
+                    offset_minutes: committer.when().offset_minutes(),
+                    message: entry.message().map(str::to_string),
+                }
+            })
+            .collect())
+    }
+
+    /// Every commit recorded in a reflog that no branch, tag or `HEAD`
+    /// reaches any more, newest first, like `git fsck --unreachable`
+    /// limited to the reflogs.
+    #[pyo3(signature = (mailmap=None))]
+    fn unreachable_commits(&self, mailmap: Option<&str>) -> PyResult<Vec<Commit>> {
+        let oids = unreachable_from_reflogs(&self.inner).map_err(git_err_to_py_err)?;
+        to_commits(&self.inner, &oids, mailmap)
+    }
+
+    /// The tips of the unreachable commits: those no other unreachable
+    /// commit has as a parent, like `git fsck --lost-found`. Each one is a
+    /// line of lost work that can be recovered by pointing a branch at it.
+    #[pyo3(signature = (mailmap=None))]
+    fn dangling_commits(&self, mailmap: Option<&str>) -> PyResult<Vec<Commit>> {
+        let oids = unreachable_from_reflogs(&self.inner).map_err(git_err_to_py_err)?;
+        let mut parents = HashSet::new();
+        for oid in &oids {
+            let commit = self.inner.find_commit(*oid).map_err(git_err_to_py_err)?;
+            parents.extend(commit.parent_ids());
+        }
+        let tips: Vec<_> = oids
+            .into_iter()
+            .filter(|oid| !parents.contains(oid))
+            .collect();
+        to_commits(&self.inner, &tips, mailmap)
+    }
+
+    /// Point `ref` at the commit `target` resolves to, creating it if
+    /// needed, and log the update with `message`, like `git update-ref -m`.
+    ///
+    /// With `expected`, the update only happens if `ref` still points at
+    /// that commit. Returns the new target's id.
+    #[pyo3(signature = (r#ref, target, message, expected=None))]
+    fn update_ref(
+        &self,
+        r#ref: &str,
+        target: &str,
+        message: &str,
+        expected: Option<&str>,
+    ) -> PyResult<String> {
+        let target = resolve_commit(&self.inner, target).map_err(git_err_to_py_err)?;
+        let reference = match expected {
+            Some(expected) => {
+                let expected = resolve_commit(&self.inner, expected).map_err(git_err_to_py_err)?;
+                self.inner
+                    .reference_matching(r#ref, target, true, expected, message)
+            }
+            None => self.inner.reference(r#ref, target, true, message),
+        }
+        .map_err(git_err_to_py_err)?;
+        let name = reference.name().unwrap_or(r#ref).to_string();
+        ensure_logged(&self.inner, &name, target, message).map_err(git_err_to_py_err)?;
+        Ok(target.to_string())
+    }
+
+    /// Append an entry for `ref` moving to `new_id` to its reflog, creating
+    /// the reflog if needed.
+    fn reflog_append(&self, r#ref: &str, new_id: &str, message: &str) -> PyResult<()> {
+        let name = full_ref_name(&self.inner, r#ref).map_err(git_err_to_py_err)?;
+        let new_id = resolve_commit(&self.inner, new_id).map_err(git_err_to_py_err)?;
+        append_reflog(&self.inner, &name, new_id, message).map_err(git_err_to_py_err)
+    }
+}
+
+fn append_reflog(
+    repo: &Repository,
+    name: &str,
+    new_id: Oid,
+    message: &str,
+) -> Result<(), git2::Error> {
+    let committer = repo.signature()?;
+    let mut reflog = repo.reflog(name)?;
+    reflog.append(new_id, &committer, Some(message))?;
+    reflog.write()
+}
+
+/// Make sure the update of `name` to `target` was logged: libgit2 only logs
+/// branches, remote-tracking refs, notes and `HEAD` on its own.
+fn ensure_logged(
+    repo: &Repository,
+    name: &str,
+    target: Oid,
+    message: &str,
+) -> Result<(), git2::Error> {
+    let reflog = repo.reflog(name)?;
+    let logged = reflog
+        .get(0)
+        .is_some_and(|entry| entry.id_new() == target && entry.message() == Some(message));
+    if logged {
+        return Ok(());
+    }
+    append_reflog(repo, name, target, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::TestRepo;
+
+    fn hashes(commits: Vec<crate::commits::Commit>) -> Vec<String> {
+        commits.into_iter().map(|commit| commit.hash).collect()
+    }
+
+    #[test]
+    fn update_ref_logs_the_move() {
+        let t = TestRepo::new();
+        let first = t.commit("file.txt", "one\n", "first");
+        let second = t.commit("file.txt", "two\n", "second");
+        let branch = t.head_name().unwrap();
+        let short = branch.trim_start_matches("refs/heads/");
+
+        let id = t
+            .repo
+            .update_ref(&branch, &first.to_string(), "move back", None)
+            .unwrap();
+        assert_eq!(id, first.to_string());
+        assert_eq!(t.git().refname_to_id(&branch).unwrap(), first);
+
+        let entries = t.repo.reflog(short).unwrap();
+        assert_eq!(entries[0].index, 0);
+        assert_eq!(entries[0].old_id, second.to_string());
+        assert_eq!(entries[0].new_id, first.to_string());
+        assert_eq!(entries[0].message.as_deref(), Some("move back"));
+        assert_eq!(entries[0].committer.email, "test@example.com");
+        let full = t.repo.reflog(&branch).unwrap();
+        assert_eq!(full.len(), entries.len());
+        assert_eq!(full[0].new_id, entries[0].new_id);
+    }
+
+    #[test]
+    fn update_ref_checks_the_expected_target() {
+        let t = TestRepo::new();
+        let first = t.commit("file.txt", "one\n", "first");
+        let second = t.commit("file.txt", "two\n", "second");
+        let branch = t.head_name().unwrap();
+
+        let stale = t
+            .repo
+            .update_ref(&branch, "HEAD~1", "stale", Some(&first.to_string()));
+        assert!(stale.is_err());
+        assert_eq!(t.git().refname_to_id(&branch).unwrap(), second);
+
+        t.repo
+            .update_ref(&branch, "HEAD~1", "current", Some(&second.to_string()))
+            .unwrap();
+        assert_eq!(t.git().refname_to_id(&branch).unwrap(), first);
+    }
+
+    #[test]
+    fn update_ref_logs_refs_libgit2_does_not() {
+        let t = TestRepo::new();
+        let first = t.commit("file.txt", "one\n", "first");
+
+        t.repo
+            .update_ref("refs/custom/marker", "HEAD", "mark", None)
+            .unwrap();
+        let entries = t.repo.reflog("refs/custom/marker").unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].new_id, first.to_string());
+        assert_eq!(entries[0].message.as_deref(), Some("mark"));
+
+        t.git()
+            .reference("refs/tags/unlogged", first, false, "tag")
+            .unwrap();
+        assert!(t.repo.reflog("refs/tags/unlogged").unwrap().is_empty());
+        t.repo
+            .reflog_append("refs/tags/unlogged", "HEAD", "noted")
+            .unwrap();
+        let entries = t.repo.reflog("refs/tags/unlogged").unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].message.as_deref(), Some("noted"));
+    }
+
+    #[test]
+    fn lost_commits_after_a_reset() {
+        let mut t = TestRepo::new();
+        let first = t.commit("file.txt", "one\n", "first");
+        // Stashes and the commits they are based on are not lost.
+        t.write("file.txt", "stashed\n");
+        let signature = t.git().signature().unwrap();
+        t.repo.inner.stash_save(&signature, "wip", None).unwrap();
+        let second = t.commit("file.txt", "two\n", "second");
+        let third = t.commit("file.txt", "three\n", "third");
+        assert!(t.repo.unreachable_commits(None).unwrap().is_empty());
+
+        let first_commit = t.git().find_commit(first).unwrap();
+        t.git()
+            .reset(first_commit.as_object(), git2::ResetType::Hard, None)
+            .unwrap();
+        // Commits made in the same second have no defined order.
+        let mut lost = hashes(t.repo.unreachable_commits(None).unwrap());
+        lost.sort();
+        let mut expected = vec![second.to_string(), third.to_string()];
+        expected.sort();
+        assert_eq!(lost, expected);
+        assert_eq!(
+            hashes(t.repo.dangling_commits(None).unwrap()),
+            vec![third.to_string()]
+        );
+
+        t.git()
+            .branch("recovered", &t.git().find_commit(third).unwrap(), false)
+            .unwrap();
+        assert!(t.repo.unreachable_commits(None).unwrap().is_empty());
+    }
+}
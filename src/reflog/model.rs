@@ -0,0 +1,40 @@
+use crate::commits::Identity;
+use crate::utils::{py_model, to_py_datetime};
+use pyo3::prelude::*;
+
+/// An entry of a ref's reflog; `index` 0 is the most recent (`HEAD@{0}`).
+#[pyclass(module = "rustygit")]
+#[derive(Clone, Debug)]
+pub struct ReflogEntry {
+    #[pyo3(get)]
+    pub index: usize,
+    /// Where the ref pointed before the update; all zeros when it was created
+    #[pyo3(get)]
+    pub old_id: String,
+    #[pyo3(get)]
+    pub new_id: String,
+    #[pyo3(get)]
+    pub committer: Identity,
+    #[pyo3(get)]
+    pub time: i64,
+    #[pyo3(get)]
+    pub offset_minutes: i32,
+    #[pyo3(get)]
+    pub message: Option<String>,
+}
+
+py_model!(
+    ReflogEntry,
+    fields: [index, old_id, new_id, committer, time, offset_minutes, message],
+    key: [index, old_id, new_id, time],
+    repr: [index, new_id, message],
+);
+
+#[pymethods]
+impl ReflogEntry {
+    /// Time of the update as a timezone-aware `datetime`
+    #[getter]
+    fn datetime(&self, py: Python) -> PyResult<PyObject> {
+        to_py_datetime(py, self.time, self.offset_minutes)
+    }
+}
@@ -1,6 +1,7 @@
-use crate::utils::git_err_to_py_err;
+use crate::contributors::{compute_contributors, Contributor};
+use crate::utils::{git_err_to_py_err, resolve_commit};
 use futures::future;
-use git2;
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use pyo3_asyncio::tokio as pyo3_tokio;
 use std::path::Path;
@@ -8,10 +9,18 @@ use tokio::task;
 
 #[pyclass(unsendable)]
 pub struct Repo {
-    inner: git2::Repository,
+    pub(crate) inner: git2::Repository,
     path: String,
 }
 
+impl Repo {
+    /// Wrap a repository opened by other means, e.g. a submodule or a
+    /// linked worktree.
+    pub(crate) fn from_repository(inner: git2::Repository, path: String) -> Self {
+        Repo { inner, path }
+    }
+}
+
 #[pymethods]
 impl Repo {
     #[new]
@@ -53,7 +62,7 @@ impl Repo {
         token: Option<&str>,
     ) -> PyResult<Self> {
         let target_path = path.map_or_else(
-            || url.split('/').last().unwrap().replace(".git", ""),
+            || url.split('/').next_back().unwrap().replace(".git", ""),
             String::from,
         );
 
@@ -137,9 +146,94 @@ impl Repo {
 
         Ok(statuses)
     }
+
+    /// Per-identity commit and line statistics, with `.mailmap` applied.
+    #[pyo3(signature = (range=None, paths=None, since=None, until=None, mailmap=None))]
+    fn contributors(
+        &self,
+        py: Python<'_>,
+        range: Option<&str>,
+        paths: Option<Vec<String>>,
+        since: Option<i64>,
+        until: Option<i64>,
+        mailmap: Option<&str>,
+    ) -> PyResult<Vec<Contributor>> {
+        let git_dir = self.inner.path().to_path_buf();
+        let paths = paths.unwrap_or_default();
+        py.allow_threads(|| compute_contributors(&git_dir, range, &paths, since, until, mailmap))
+    }
+
+    /// Best common ancestor of two revisions, or `None` if they share no history.
+    fn merge_base(&self, a: &str, b: &str) -> PyResult<Option<String>> {
+        let a = resolve_commit(&self.inner, a).map_err(git_err_to_py_err)?;
+        let b = resolve_commit(&self.inner, b).map_err(git_err_to_py_err)?;
+        match self.inner.merge_base(a, b) {
+            Ok(oid) => Ok(Some(oid.to_string())),
+            Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+            Err(e) => Err(git_err_to_py_err(e)),
+        }
+    }
+
+    /// All merge bases of two or more revisions, like `git merge-base --all`.
+    ///
+    /// With `octopus=True` the bases are computed for an octopus merge of all
+    /// revisions, like `git merge-base --octopus`.
+    #[pyo3(signature = (*revs, octopus=false))]
+    fn merge_bases(&self, revs: Vec<String>, octopus: bool) -> PyResult<Vec<String>> {
+        if revs.len() < 2 {
+            return Err(PyValueError::new_err(
+                "merge_bases requires at least two revisions",
+            ));
+        }
+        let oids = revs
+            .iter()
+            .map(|rev| resolve_commit(&self.inner, rev))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(git_err_to_py_err)?;
+
+        let bases = if octopus {
+            self.inner.merge_base_octopus(&oids).map(|oid| vec![oid])
+        } else if oids.len() == 2 {
+            self.inner
+                .merge_bases(oids[0], oids[1])
+                .map(|bases| bases.iter().copied().collect())
+        } else {
+            self.inner
+                .merge_bases_many(&oids)
+                .map(|bases| bases.iter().copied().collect())
+        };
+        match bases {
+            Ok(bases) => Ok(bases.iter().map(|oid| oid.to_string()).collect()),
+            Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(Vec::new()),
+            Err(e) => Err(git_err_to_py_err(e)),
+        }
+    }
+
+    /// Whether `ancestor` is reachable from `descendant`. A commit counts as
+    /// its own ancestor, as with `git merge-base --is-ancestor`.
+    fn is_ancestor(&self, ancestor: &str, descendant: &str) -> PyResult<bool> {
+        let ancestor = resolve_commit(&self.inner, ancestor).map_err(git_err_to_py_err)?;
+        let descendant = resolve_commit(&self.inner, descendant).map_err(git_err_to_py_err)?;
+        if ancestor == descendant {
+            return Ok(true);
+        }
+        self.inner
+            .graph_descendant_of(descendant, ancestor)
+            .map_err(git_err_to_py_err)
+    }
+
+    /// Number of commits `local` has that `upstream` lacks, and vice versa.
+    fn ahead_behind(&self, local: &str, upstream: &str) -> PyResult<(usize, usize)> {
+        let local = resolve_commit(&self.inner, local).map_err(git_err_to_py_err)?;
+        let upstream = resolve_commit(&self.inner, upstream).map_err(git_err_to_py_err)?;
+        self.inner
+            .graph_ahead_behind(local, upstream)
+            .map_err(git_err_to_py_err)
+    }
+
     #[staticmethod]
     fn async_clone(
-        py: Python,
+        py: Python<'_>,
         urls: Vec<String>,
         base_dir: Option<String>,
         username: Option<String>,
@@ -154,7 +248,7 @@ impl Repo {
                 let target_path = format!(
                     "{}/{}",
                     base_dir,
-                    url.split('/').last().unwrap().replace(".git", "")
+                    url.split('/').next_back().unwrap().replace(".git", "")
                 );
 
                 task::spawn_blocking(move || {
@@ -187,3 +281,86 @@ impl Repo {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::TestRepo;
+    use git2::Oid;
+    use pyo3::exceptions::PyValueError;
+    use pyo3::prelude::*;
+
+    /// `base`, then two commits on the default branch and one on `topic`,
+    /// plus an `orphan` branch with no history in common.
+    fn branches() -> (TestRepo, Oid) {
+        let t = TestRepo::new();
+        let base = t.commit("file.txt", "base\n", "base");
+        let main = t.head_name().unwrap();
+        t.checkout_new_branch("topic", base);
+        t.commit("topic.txt", "topic\n", "topic");
+        t.checkout_new_branch("other", base);
+        t.commit("other.txt", "other\n", "other");
+        t.checkout(&main);
+        t.commit("a.txt", "a\n", "a");
+        t.commit("b.txt", "b\n", "b");
+
+        t.orphan("orphan");
+        (t, base)
+    }
+
+    fn revs(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn merge_base_of_diverged_branches() {
+        let (t, base) = branches();
+        let base = base.to_string();
+
+        assert_eq!(
+            t.repo.merge_base("HEAD", "topic").unwrap(),
+            Some(base.clone())
+        );
+        assert_eq!(t.repo.merge_base("HEAD", "orphan").unwrap(), None);
+        assert!(t.repo.merge_base("HEAD", "missing").is_err());
+
+        assert_eq!(
+            t.repo.merge_bases(revs(&["HEAD", "topic"]), false).unwrap(),
+            vec![base.clone()]
+        );
+        assert_eq!(
+            t.repo
+                .merge_bases(revs(&["HEAD", "topic", "other"]), true)
+                .unwrap(),
+            vec![base.clone()]
+        );
+        assert_eq!(
+            t.repo
+                .merge_bases(revs(&["HEAD", "topic", "other"]), false)
+                .unwrap(),
+            vec![base]
+        );
+        assert!(t
+            .repo
+            .merge_bases(revs(&["HEAD", "orphan"]), false)
+            .unwrap()
+            .is_empty());
+
+        let err = t.repo.merge_bases(revs(&["HEAD"]), false).unwrap_err();
+        Python::with_gil(|py| assert!(err.is_instance_of::<PyValueError>(py)));
+    }
+
+    #[test]
+    fn ancestry_and_ahead_behind() {
+        let (t, base) = branches();
+        let base = base.to_string();
+
+        assert!(t.repo.is_ancestor(&base, "HEAD").unwrap());
+        assert!(t.repo.is_ancestor("HEAD", "HEAD").unwrap());
+        assert!(!t.repo.is_ancestor("HEAD", &base).unwrap());
+        assert!(!t.repo.is_ancestor("topic", "HEAD").unwrap());
+
+        assert_eq!(t.repo.ahead_behind("HEAD", "topic").unwrap(), (2, 1));
+        assert_eq!(t.repo.ahead_behind("topic", "HEAD").unwrap(), (1, 2));
+        assert_eq!(t.repo.ahead_behind("HEAD~2", &base).unwrap(), (0, 0));
+    }
+}
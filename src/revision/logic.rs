@@ -0,0 +1,191 @@
+use crate::commits::Commit;
+use crate::repo::Repo;
+use crate::utils::{git_err_to_py_err, load_mailmap, resolve_commit};
+use git2::RevparseMode;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use super::model::{GitObject, RevRange};
+
+#[pymethods]
+impl Repo {
+    /// Resolve a revspec such as `HEAD~3`, `v1.2^{tree}` or `main@{upstream}`.
+    fn rev_parse(&self, spec: &str) -> PyResult<GitObject> {
+        let object = self
+            .inner
+            .revparse_single(spec)
+            .map_err(git_err_to_py_err)?;
+        GitObject::from_git_object(&object).map_err(git_err_to_py_err)
+    }
+
+    /// Resolve an `A..B` or `A...B` range into its endpoints.
+    fn rev_parse_range(&self, spec: &str) -> PyResult<RevRange> {
+        let revspec = self.inner.revparse(spec).map_err(git_err_to_py_err)?;
+        let mode = revspec.mode();
+        if !mode.contains(RevparseMode::RANGE) {
+            return Err(PyValueError::new_err(format!(
+                "'{}' is not a revision range",
+                spec
+            )));
+        }
+
+        let start = revspec
+            .from()
+            .map(GitObject::from_git_object)
+            .transpose()
+            .map_err(git_err_to_py_err)?;
+        let end = revspec
+            .to()
+            .map(GitObject::from_git_object)
+            .transpose()
+            .map_err(git_err_to_py_err)?;
+
+        let symmetric = mode.contains(RevparseMode::MERGE_BASE);
+        let merge_base = match (symmetric, revspec.from(), revspec.to()) {
+            (true, Some(from), Some(to)) => match self.inner.merge_base(from.id(), to.id()) {
+                Ok(oid) => Some(oid.to_string()),
+                Err(e) if e.code() == git2::ErrorCode::NotFound => None,
+                Err(e) => return Err(git_err_to_py_err(e)),
+            },
+            _ => None,
+        };
+
+        Ok(RevRange {
+            start,
+            end,
+            symmetric,
+            merge_base,
+        })
+    }
+
+    /// Shortest unambiguous abbreviation of the object `rev` resolves to,
+    /// honouring `core.abbrev`.
+    fn short_id(&self, rev: &str) -> PyResult<String> {
+        let object = self.inner.revparse_single(rev).map_err(git_err_to_py_err)?;
+        let short_id = object.short_id().map_err(git_err_to_py_err)?;
+        Ok(short_id.as_str().unwrap_or("").to_string())
+    }
+
+    /// Look up the commit `rev` resolves to, with `.mailmap` applied.
+    #[pyo3(signature = (rev="HEAD", mailmap=None))]
+    fn commit(&self, rev: &str, mailmap: Option<&str>) -> PyResult<Commit> {
+        let oid = resolve_commit(&self.inner, rev).map_err(git_err_to_py_err)?;
+        let commit = self.inner.find_commit(oid).map_err(git_err_to_py_err)?;
+        let mailmap = load_mailmap(&self.inner, mailmap).map_err(git_err_to_py_err)?;
+        Ok(Commit::from_git_commit(&commit, Some(&mailmap)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::TestRepo;
+    use pyo3::exceptions::PyValueError;
+    use pyo3::prelude::*;
+
+    #[test]
+    fn rev_parse_resolves_objects_of_every_kind() {
+        let t = TestRepo::new();
+        let first = t.commit("file.txt", "one\n", "first");
+        let second = t.commit("file.txt", "two\n", "second");
+        let git = t.git();
+        let signature = git.signature().unwrap();
+        let target = git.find_object(first, None).unwrap();
+        let tag = git.tag("v1", &target, &signature, "v1", false).unwrap();
+
+        let parent = t.repo.rev_parse("HEAD~1").unwrap();
+        assert_eq!(
+            (parent.kind.as_str(), parent.id),
+            ("commit", first.to_string())
+        );
+        let tree = t.repo.rev_parse("HEAD^{tree}").unwrap();
+        assert_eq!(tree.kind, "tree");
+        assert_eq!(
+            tree.id,
+            git.find_commit(second).unwrap().tree_id().to_string()
+        );
+        let blob = t.repo.rev_parse("HEAD:file.txt").unwrap();
+        assert_eq!(blob.kind, "blob");
+        assert_eq!(blob.id, git.blob(b"two\n").unwrap().to_string());
+        let annotated = t.repo.rev_parse("v1").unwrap();
+        assert_eq!(
+            (annotated.kind.as_str(), annotated.id),
+            ("tag", tag.to_string())
+        );
+        assert_eq!(
+            t.repo.rev_parse("v1^{commit}").unwrap().id,
+            first.to_string()
+        );
+        assert!(second
+            .to_string()
+            .starts_with(&t.repo.rev_parse("HEAD").unwrap().short_id));
+        assert!(t.repo.rev_parse("HEAD~5").is_err());
+    }
+
+    #[test]
+    fn rev_parse_follows_the_upstream_branch() {
+        let t = TestRepo::new();
+        let base = t.commit("file.txt", "base\n", "base");
+        let main = t.head_name().unwrap();
+        let short = main.trim_start_matches("refs/heads/").to_string();
+        t.checkout_new_branch("topic", base);
+        let topic = t.commit("file.txt", "topic\n", "topic");
+        t.checkout(&main);
+        let mut config = t.git().config().unwrap();
+        config
+            .set_str(&format!("branch.{}.remote", short), ".")
+            .unwrap();
+        config
+            .set_str(&format!("branch.{}.merge", short), "refs/heads/topic")
+            .unwrap();
+
+        let upstream = t
+            .repo
+            .rev_parse(&format!("{}@{{upstream}}", short))
+            .unwrap();
+        assert_eq!(upstream.id, topic.to_string());
+    }
+
+    #[test]
+    fn rev_parse_range_reports_endpoints_and_merge_base() {
+        let t = TestRepo::new();
+        let base = t.commit("file.txt", "base\n", "base");
+        let main = t.head_name().unwrap();
+        t.checkout_new_branch("topic", base);
+        let topic = t.commit("topic.txt", "topic\n", "topic");
+        t.checkout(&main);
+        let tip = t.commit("main.txt", "main\n", "main");
+
+        let range = t.repo.rev_parse_range("topic..HEAD").unwrap();
+        assert_eq!(range.start.unwrap().id, topic.to_string());
+        assert_eq!(range.end.unwrap().id, tip.to_string());
+        assert!(!range.symmetric);
+        assert_eq!(range.merge_base, None);
+
+        let symmetric = t.repo.rev_parse_range("topic...HEAD").unwrap();
+        assert!(symmetric.symmetric);
+        assert_eq!(symmetric.merge_base, Some(base.to_string()));
+
+        let err = t.repo.rev_parse_range("HEAD").unwrap_err();
+        Python::with_gil(|py| assert!(err.is_instance_of::<PyValueError>(py)));
+    }
+
+    #[test]
+    fn short_id_and_commit_lookup() {
+        let t = TestRepo::new();
+        let first = t.commit("file.txt", "one\n", "first");
+        t.commit("file.txt", "two\n", "second");
+
+        let short = t.repo.short_id("HEAD~1").unwrap();
+        assert!(short.len() >= 7);
+        assert!(first.to_string().starts_with(&short));
+
+        let commit = t.repo.commit("HEAD~1", None).unwrap();
+        assert_eq!(commit.hash, first.to_string());
+        assert_eq!(commit.author, "Test User");
+        let mailmap = "Canonical <canonical@example.com> <test@example.com>\n";
+        let mapped = t.repo.commit("HEAD", Some(mailmap)).unwrap();
+        assert_eq!(mapped.author, "Canonical");
+        assert_eq!(mapped.parents, vec![first.to_string()]);
+        assert!(t.repo.commit("HEAD^{tree}", None).is_err());
+    }
+}
@@ -0,0 +1,4 @@
+pub mod logic;
+pub mod model;
+
+pub use model::{GitObject, RevRange};
@@ -0,0 +1,55 @@
+use crate::utils::py_model;
+use pyo3::prelude::*;
+
+/// A resolved Git object: a commit, tree, blob or annotated tag.
+#[pyclass(module = "rustygit")]
+#[derive(Clone, Debug)]
+pub struct GitObject {
+    #[pyo3(get)]
+    pub id: String,
+    #[pyo3(get)]
+    pub short_id: String,
+    #[pyo3(get)]
+    pub kind: String,
+}
+
+py_model!(
+    GitObject,
+    fields: [id, short_id, kind],
+    key: [id],
+    repr: [kind, id],
+);
+
+impl GitObject {
+    pub fn from_git_object(object: &git2::Object) -> Result<Self, git2::Error> {
+        let short_id = object.short_id()?;
+        Ok(GitObject {
+            id: object.id().to_string(),
+            short_id: short_id.as_str().unwrap_or("").to_string(),
+            kind: object.kind().map_or("any", |kind| kind.str()).to_string(),
+        })
+    }
+}
+
+/// The endpoints of a revision range such as `A..B` or `A...B`.
+#[pyclass(module = "rustygit")]
+#[derive(Clone, Debug)]
+pub struct RevRange {
+    #[pyo3(get)]
+    pub start: Option<GitObject>,
+    #[pyo3(get)]
+    pub end: Option<GitObject>,
+    /// True for the symmetric difference `A...B`
+    #[pyo3(get)]
+    pub symmetric: bool,
+    /// Merge base of the endpoints, set for `A...B` only
+    #[pyo3(get)]
+    pub merge_base: Option<String>,
+}
+
+py_model!(
+    RevRange,
+    fields: [start, end, symmetric, merge_base],
+    key: [start, end, symmetric],
+    repr: [start, end, symmetric],
+);
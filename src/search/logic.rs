@@ -0,0 +1,624 @@
+use crate::commits::logic::diff_hunks;
+use crate::commits::{Commit, DiffHunk};
+use crate::repo::Repo;
+use crate::tree::matching_blobs;
+use crate::utils::{git_err_to_py_err, load_mailmap, walk_revisions};
+use git2::{DiffOptions, Mailmap, Oid, Patch, Pathspec, PathspecFlags, Repository};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use rayon::prelude::*;
+use regex::bytes::Regex;
+use std::path::{Path, PathBuf};
+
+use super::model::{GrepMatch, PickaxeMatch};
+
+/// How a diff must relate to the pattern for its commit to be reported.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PickaxeMode {
+    /// The number of occurrences in a file differs before and after (`-S`)
+    Occurrences,
+    /// An added or removed line matches (`-G`)
+    ChangedLines,
+}
+
+impl PickaxeMode {
+    fn parse(mode: &str) -> PyResult<Self> {
+        match mode {
+            "S" => Ok(PickaxeMode::Occurrences),
+            "G" => Ok(PickaxeMode::ChangedLines),
+            other => Err(PyValueError::new_err(format!(
+                "Unknown pickaxe mode '{}', expected 'S' or 'G'",
+                other
+            ))),
+        }
+    }
+}
+
+/// Build the matcher for a pickaxe or grep pattern, escaping it unless it
+/// is a regular expression.
+pub fn compile_pattern(pattern: &str, regex: bool, ignore_case: bool) -> PyResult<Regex> {
+    let source = if regex {
+        pattern.to_string()
+    } else {
+        regex::escape(pattern)
+    };
+    regex::bytes::RegexBuilder::new(&source)
+        .case_insensitive(ignore_case)
+        .build()
+        .map_err(|e| PyValueError::new_err(format!("Invalid pattern: {}", e)))
+}
+
+/// Non-merge commits reachable from `range` whose diff against their parent
+/// changes `matcher` according to `mode`, in revwalk order.
+pub fn pickaxe_search(
+    git_dir: &Path,
+    matcher: &Regex,
+    mode: PickaxeMode,
+    paths: &[String],
+    range: Option<&str>,
+    mailmap: Option<&str>,
+) -> Result<Vec<PickaxeMatch>, git2::Error> {
+    let repo = Repository::open(git_dir)?;
+    let oids = walk_revisions(&repo, range)?;
+
+    let matches = oids
+        .par_iter()
+        .map_init(
+            || {
+                let repo = Repository::open(git_dir)?;
+                let mailmap = load_mailmap(&repo, mailmap)?;
+                Ok::<_, git2::Error>((repo, mailmap))
+            },
+            |state, oid| match state {
+                Ok((repo, mailmap)) => commit_matches(repo, mailmap, *oid, matcher, mode, paths),
+                Err(e) => Err(git2::Error::new(e.code(), e.class(), e.message())),
+            },
+        )
+        .collect::<Result<Vec<_>, git2::Error>>()?;
+
+    Ok(matches.into_iter().flatten().collect())
+}
+
+fn commit_matches(
+    repo: &Repository,
+    mailmap: &Mailmap,
+    oid: Oid,
+    matcher: &Regex,
+    mode: PickaxeMode,
+    paths: &[String],
+) -> Result<Option<PickaxeMatch>, git2::Error> {
+    let commit = repo.find_commit(oid)?;
+    // Like `git log -S`, merges are not diffed.
+    if commit.parent_count() > 1 {
+        return Ok(None);
+    }
+    let parent_tree = match commit.parent(0) {
+        Ok(parent) => Some(parent.tree()?),
+        Err(_) => None,
+    };
+    let tree = commit.tree()?;
+
+    let mut options = DiffOptions::new();
+    for path in paths {
+        options.pathspec(path);
+    }
+    let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut options))?;
+
+    let mut matched_paths = Vec::new();
+    let mut hunks = Vec::new();
+    for (idx, delta) in diff.deltas().enumerate() {
+        let path = delta
+            .new_file()
+            .path()
+            .or_else(|| delta.old_file().path())
+            .and_then(|p| p.to_str())
+            .unwrap_or("")
+            .to_string();
+
+        if mode == PickaxeMode::Occurrences {
+            let old = occurrences(repo, delta.old_file().id(), matcher)?;
+            let new = occurrences(repo, delta.new_file().id(), matcher)?;
+            match (old, new) {
+                (Some(old), Some(new)) if old != new => {}
+                _ => continue,
+            }
+        }
+
+        let Some(patch) = Patch::from_diff(&diff, idx)? else {
+            continue;
+        };
+        let file_hunks = diff_hunks(&patch, &path)?;
+        let matching: Vec<DiffHunk> = file_hunks
+            .iter()
+            .filter(|hunk| hunk_matches(hunk, matcher))
+            .cloned()
+            .collect();
+
+        match mode {
+            PickaxeMode::ChangedLines if matching.is_empty() => continue,
+            // The count can change without any single line matching, e.g.
+            // when a line break is inserted into an occurrence.
+            PickaxeMode::Occurrences if matching.is_empty() => hunks.extend(file_hunks),
+            _ => hunks.extend(matching),
+        }
+        matched_paths.push(path);
+    }
+
+    if matched_paths.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(PickaxeMatch {
+        commit: Commit::from_git_commit(&commit, Some(mailmap)),
+        paths: matched_paths,
+        hunks,
+    }))
+}
+
+/// Occurrences of `matcher` in a blob (zero for a missing one), or `None`
+/// for a binary blob.
+fn occurrences(repo: &Repository, id: Oid, matcher: &Regex) -> Result<Option<usize>, git2::Error> {
+    if id.is_zero() {
+        return Ok(Some(0));
+    }
+    let blob = repo.find_blob(id)?;
+    if blob.is_binary() {
+        return Ok(None);
+    }
+    Ok(Some(matcher.find_iter(blob.content()).count()))
+}
+
+fn hunk_matches(hunk: &DiffHunk, matcher: &Regex) -> bool {
+    hunk.lines.iter().any(|line| {
+        (line.starts_with('+') || line.starts_with('-')) && matcher.is_match(&line.as_bytes()[1..])
+    })
+}
+
+#[pymethods]
+impl Repo {
+    /// Find the commits that introduced or removed `pattern`, like
+    /// `git log -S` (`mode="S"`) or `git log -G` (`mode="G"`).
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (pattern, regex=false, paths=None, range=None, mode="S", ignore_case=false, mailmap=None))]
+    fn pickaxe(
+        &self,
+        py: Python<'_>,
+        pattern: &str,
+        regex: bool,
+        paths: Option<Vec<String>>,
+        range: Option<&str>,
+        mode: &str,
+        ignore_case: bool,
+        mailmap: Option<&str>,
+    ) -> PyResult<Vec<PickaxeMatch>> {
+        let mode = PickaxeMode::parse(mode)?;
+        let matcher = compile_pattern(pattern, regex, ignore_case)?;
+        let git_dir = self.inner.path().to_path_buf();
+        let paths = paths.unwrap_or_default();
+        py.allow_threads(|| pickaxe_search(&git_dir, &matcher, mode, &paths, range, mailmap))
+            .map_err(git_err_to_py_err)
+    }
+}
+
+/// Where `grep` reads a file's content from.
+enum GrepSource {
+    Blob(Oid),
+    File(PathBuf),
+}
+
+/// What `grep` searches: a revision's tree, the index, or the working tree.
+pub enum GrepTarget<'a> {
+    Revision(&'a str),
+    Index,
+    Worktree,
+}
+
+/// Options shared by every file searched by `grep`.
+pub struct GrepOptions {
+    /// Most matches reported for each file, like `git grep -m`.
+    pub max_count: Option<usize>,
+    pub context_lines: usize,
+}
+
+/// Search the files of `target` matching `paths` for `matcher`, in parallel.
+/// Binary files are skipped. Matches are ordered by path and line.
+pub fn grep_files(
+    git_dir: &Path,
+    matcher: &Regex,
+    target: GrepTarget,
+    paths: &[String],
+    options: &GrepOptions,
+) -> Result<Vec<GrepMatch>, git2::Error> {
+    let repo = Repository::open(git_dir)?;
+    let files: Vec<(String, GrepSource)> = match target {
+        GrepTarget::Revision(rev) => {
+            let tree = repo.revparse_single(rev)?.peel_to_tree()?;
+            matching_blobs(&repo, &tree, paths, None)?
+                .into_iter()
+                .map(|blob| (blob.path, GrepSource::Blob(blob.id)))
+                .collect()
+        }
+        GrepTarget::Index | GrepTarget::Worktree => {
+            let workdir =
+                match target {
+                    GrepTarget::Worktree => Some(repo.workdir().ok_or_else(|| {
+                        git2::Error::from_str("Bare repository has no working tree")
+                    })?),
+                    _ => None,
+                };
+            let pathspec = Pathspec::new(paths.iter())?;
+            let mut files = Vec::new();
+            for entry in repo.index()?.iter() {
+                let path = String::from_utf8_lossy(&entry.path).into_owned();
+                // Skip submodules and conflict stages other than "ours".
+                let stage = (entry.flags >> 12) & 0x3;
+                if entry.mode == 0o160000 || stage != 0 && stage != 2 {
+                    continue;
+                }
+                if !pathspec.matches_path(Path::new(&path), PathspecFlags::DEFAULT) {
+                    continue;
+                }
+                let source = match workdir {
+                    Some(workdir) => GrepSource::File(workdir.join(&path)),
+                    None => GrepSource::Blob(entry.id),
+                };
+                files.push((path, source));
+            }
+            files.dedup_by(|a, b| a.0 == b.0);
+            files
+        }
+    };
+
+    let per_file = files
+        .par_iter()
+        .map_init(
+            || Repository::open(git_dir),
+            |repo, (path, source)| {
+                let repo = repo
+                    .as_ref()
+                    .map_err(|e| git2::Error::new(e.code(), e.class(), e.message()))?;
+                let content = match source {
+                    GrepSource::Blob(id) => repo.find_blob(*id)?.content().to_vec(),
+                    // Files deleted from the working tree are simply not searched.
+                    GrepSource::File(file) => match std::fs::read(file) {
+                        Ok(content) => content,
+                        Err(_) => return Ok(Vec::new()),
+                    },
+                };
+                Ok(grep_content(path, &content, matcher, options))
+            },
+        )
+        .collect::<Result<Vec<_>, git2::Error>>()?;
+
+    Ok(per_file.into_iter().flatten().collect())
+}
+
+/// Git's binary heuristic: a NUL byte within the first 8000 bytes.
+fn looks_binary(content: &[u8]) -> bool {
+    content[..content.len().min(8000)].contains(&0)
+}
+
+fn grep_content(
+    path: &str,
+    content: &[u8],
+    matcher: &Regex,
+    options: &GrepOptions,
+) -> Vec<GrepMatch> {
+    if looks_binary(content) {
+        return Vec::new();
+    }
+    let lines: Vec<&[u8]> = content
+        .split(|&b| b == b'\n')
+        .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+        .collect();
+    // A trailing newline does not start another line.
+    let line_count = if content.ends_with(b"\n") {
+        lines.len() - 1
+    } else {
+        lines.len()
+    };
+    let text = |line: &[u8]| String::from_utf8_lossy(line).into_owned();
+
+    let mut matches = Vec::new();
+    for (i, line) in lines[..line_count].iter().enumerate() {
+        if options.max_count.is_some_and(|max| matches.len() >= max) {
+            break;
+        }
+        let Some(found) = matcher.find(line) else {
+            continue;
+        };
+        let before_start = i.saturating_sub(options.context_lines);
+        let after_end = (i + 1 + options.context_lines).min(line_count);
+        matches.push(GrepMatch {
+            path: path.to_string(),
+            line_number: i + 1,
+            column: String::from_utf8_lossy(&line[..found.start()])
+                .chars()
+                .count()
+                + 1,
+            line: text(line),
+            before: lines[before_start..i].iter().map(|l| text(l)).collect(),
+            after: lines[i + 1..after_end].iter().map(|l| text(l)).collect(),
+        });
+    }
+    matches
+}
+
+#[pymethods]
+impl Repo {
+    /// Search file contents like `git grep`.
+    ///
+    /// Searches the tree of `rev` when given, otherwise the index with
+    /// `cached=True`, otherwise the tracked files in the working tree.
+    /// `max_count` limits the matches reported for each file, not in total.
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (
+        pattern,
+        rev=None,
+        paths=None,
+        ignore_case=false,
+        regex=false,
+        max_count=None,
+        context_lines=0,
+        cached=false
+    ))]
+    fn grep(
+        &self,
+        py: Python<'_>,
+        pattern: &str,
+        rev: Option<&str>,
+        paths: Option<Vec<String>>,
+        ignore_case: bool,
+        regex: bool,
+        max_count: Option<usize>,
+        context_lines: usize,
+        cached: bool,
+    ) -> PyResult<Vec<GrepMatch>> {
+        let matcher = compile_pattern(pattern, regex, ignore_case)?;
+        let target = match (rev, cached) {
+            (Some(_), true) => {
+                return Err(PyValueError::new_err(
+                    "Pass either rev or cached=True, not both",
+                ));
+            }
+            (Some(rev), false) => GrepTarget::Revision(rev),
+            (None, true) => GrepTarget::Index,
+            (None, false) => GrepTarget::Worktree,
+        };
+        let options = GrepOptions {
+            max_count,
+            context_lines,
+        };
+        let git_dir = self.inner.path().to_path_buf();
+        let paths = paths.unwrap_or_default();
+        py.allow_threads(|| grep_files(&git_dir, &matcher, target, &paths, &options))
+            .map_err(git_err_to_py_err)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::TestRepo;
+    use pyo3::exceptions::PyValueError;
+    use pyo3::prelude::*;
+    use std::path::Path;
+
+    struct Query<'a> {
+        pattern: &'a str,
+        rev: Option<&'a str>,
+        paths: Option<Vec<String>>,
+        ignore_case: bool,
+        regex: bool,
+        max_count: Option<usize>,
+        context_lines: usize,
+        cached: bool,
+    }
+
+    impl<'a> Query<'a> {
+        fn new(pattern: &'a str) -> Self {
+            Query {
+                pattern,
+                rev: None,
+                paths: None,
+                ignore_case: false,
+                regex: false,
+                max_count: None,
+                context_lines: 0,
+                cached: false,
+            }
+        }
+
+        fn run(self, t: &TestRepo) -> PyResult<Vec<crate::search::GrepMatch>> {
+            Python::with_gil(|py| {
+                t.repo.grep(
+                    py,
+                    self.pattern,
+                    self.rev,
+                    self.paths,
+                    self.ignore_case,
+                    self.regex,
+                    self.max_count,
+                    self.context_lines,
+                    self.cached,
+                )
+            })
+        }
+
+        fn hits(self, t: &TestRepo) -> Vec<(String, usize)> {
+            self.run(t)
+                .unwrap()
+                .into_iter()
+                .map(|m| (m.path, m.line_number))
+                .collect()
+        }
+    }
+
+    fn hit(path: &str, line: usize) -> (String, usize) {
+        (path.to_string(), line)
+    }
+
+    fn stage(t: &TestRepo, path: &str, content: &str) {
+        t.write(path, content);
+        let mut index = t.git().index().unwrap();
+        index.add_path(Path::new(path)).unwrap();
+        index.write().unwrap();
+    }
+
+    #[test]
+    fn grep_searches_the_worktree_index_or_a_revision() {
+        let t = TestRepo::new();
+        t.commit("a.txt", "alpha\nbeta\n", "add a");
+        stage(&t, "a.txt", "alpha\ngamma\n");
+        t.write("a.txt", "alpha\ndelta\n");
+        t.write("untracked.txt", "delta\n");
+
+        assert_eq!(Query::new("delta").hits(&t), vec![hit("a.txt", 2)]);
+        assert!(Query::new("gamma").hits(&t).is_empty());
+        let cached = Query {
+            cached: true,
+            ..Query::new("gamma")
+        };
+        assert_eq!(cached.hits(&t), vec![hit("a.txt", 2)]);
+        let at_head = Query {
+            rev: Some("HEAD"),
+            ..Query::new("beta")
+        };
+        assert_eq!(at_head.hits(&t), vec![hit("a.txt", 2)]);
+    }
+
+    #[test]
+    fn grep_rejects_a_revision_with_cached() {
+        let t = TestRepo::new();
+        t.commit("a.txt", "alpha\n", "add a");
+        let both = Query {
+            rev: Some("HEAD"),
+            cached: true,
+            ..Query::new("alpha")
+        };
+        let err = both.run(&t).unwrap_err();
+        Python::with_gil(|py| assert!(err.is_instance_of::<PyValueError>(py)));
+    }
+
+    #[test]
+    fn grep_max_count_applies_to_each_file() {
+        let t = TestRepo::new();
+        t.commit("a.txt", "x\nx\nx\n", "add a");
+        t.commit("b.txt", "x\nx\nx\n", "add b");
+        let limited = Query {
+            max_count: Some(2),
+            ..Query::new("x")
+        };
+        assert_eq!(
+            limited.hits(&t),
+            vec![
+                hit("a.txt", 1),
+                hit("a.txt", 2),
+                hit("b.txt", 1),
+                hit("b.txt", 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn grep_reports_columns_and_context() {
+        let t = TestRepo::new();
+        t.commit("a.txt", "one\ntwo\nthé Needle\nfour\n", "add a");
+        t.commit("b.bin", "needle\0\n", "add binary");
+        t.commit("c.txt", "needle\n", "add c");
+
+        let query = Query {
+            ignore_case: true,
+            context_lines: 1,
+            paths: Some(vec!["*.txt".to_string(), "*.bin".to_string()]),
+            ..Query::new("needle")
+        };
+        let matches = query.run(&t).unwrap();
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].path, "a.txt");
+        assert_eq!(matches[0].line_number, 3);
+        assert_eq!(matches[0].column, 5);
+        assert_eq!(matches[0].line, "thé Needle");
+        assert_eq!(matches[0].before, vec!["two"]);
+        assert_eq!(matches[0].after, vec!["four"]);
+        assert_eq!(matches[1].path, "c.txt");
+        assert!(matches[1].before.is_empty() && matches[1].after.is_empty());
+
+        let only_a = Query {
+            regex: true,
+            paths: Some(vec!["a.txt".to_string()]),
+            ..Query::new("^t[a-z]+$")
+        };
+        assert_eq!(only_a.hits(&t), vec![hit("a.txt", 2)]);
+        assert!(Query {
+            regex: true,
+            ..Query::new("(")
+        }
+        .run(&t)
+        .is_err());
+    }
+
+    fn pickaxe(t: &TestRepo, pattern: &str, mode: &str, paths: Option<Vec<String>>) -> Vec<String> {
+        Python::with_gil(|py| {
+            t.repo
+                .pickaxe(py, pattern, false, paths, None, mode, false, None)
+                .unwrap()
+                .into_iter()
+                .map(|m| m.commit.message.trim().to_string())
+                .collect()
+        })
+    }
+
+    #[test]
+    fn pickaxe_s_finds_commits_that_change_the_count() {
+        let t = TestRepo::new();
+        t.commit("a.txt", "keep\nneedle\n", "add needle");
+        t.commit("a.txt", "needle\nkeep\n", "move needle");
+        t.commit("b.txt", "needle\n", "copy needle");
+        t.commit("a.txt", "keep\n", "drop needle");
+
+        assert_eq!(
+            pickaxe(&t, "needle", "S", None),
+            vec!["drop needle", "copy needle", "add needle"]
+        );
+        assert_eq!(
+            pickaxe(&t, "needle", "S", Some(vec!["b.txt".to_string()])),
+            vec!["copy needle"]
+        );
+    }
+
+    #[test]
+    fn pickaxe_g_finds_commits_with_matching_changed_lines() {
+        let t = TestRepo::new();
+        t.commit("a.txt", "needle one\n", "add needle");
+        t.commit("a.txt", "needle two\n", "edit needle");
+        t.commit("a.txt", "needle two\nother\n", "unrelated");
+
+        assert_eq!(
+            pickaxe(&t, "needle", "G", None),
+            vec!["edit needle", "add needle"]
+        );
+        assert_eq!(pickaxe(&t, "needle", "S", None), vec!["add needle"]);
+        let matches = Python::with_gil(|py| {
+            t.repo
+                .pickaxe(py, "NEEDLE", false, None, None, "G", true, None)
+                .unwrap()
+        });
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].paths, vec!["a.txt"]);
+        assert!(matches[0]
+            .hunks
+            .iter()
+            .all(|hunk| hunk.lines.iter().any(|line| line.contains("needle"))));
+    }
+
+    #[test]
+    fn pickaxe_rejects_unknown_modes() {
+        let t = TestRepo::new();
+        t.commit("a.txt", "needle\n", "add needle");
+        Python::with_gil(|py| {
+            let err = t
+                .repo
+                .pickaxe(py, "needle", false, None, None, "X", false, None)
+                .unwrap_err();
+            assert!(err.is_instance_of::<PyValueError>(py));
+        });
+    }
+}
@@ -0,0 +1,47 @@
+use crate::commits::{Commit, DiffHunk};
+use crate::utils::py_model;
+use pyo3::prelude::*;
+
+/// A commit whose diff against its parent adds or removes the searched pattern.
+#[pyclass(module = "rustygit")]
+#[derive(Clone, Debug)]
+pub struct PickaxeMatch {
+    #[pyo3(get)]
+    pub commit: Commit,
+    #[pyo3(get)]
+    pub paths: Vec<String>,
+    #[pyo3(get)]
+    pub hunks: Vec<DiffHunk>,
+}
+
+py_model!(
+    PickaxeMatch,
+    fields: [commit, paths, hunks],
+    key: [commit],
+    repr: [commit, paths],
+);
+
+/// A line matched by `Repo.grep`.
+#[pyclass(module = "rustygit")]
+#[derive(Clone, Debug)]
+pub struct GrepMatch {
+    #[pyo3(get)]
+    pub path: String,
+    #[pyo3(get)]
+    pub line_number: usize,
+    #[pyo3(get)]
+    pub column: usize,
+    #[pyo3(get)]
+    pub line: String,
+    #[pyo3(get)]
+    pub before: Vec<String>,
+    #[pyo3(get)]
+    pub after: Vec<String>,
+}
+
+py_model!(
+    GrepMatch,
+    fields: [path, line_number, column, line, before, after],
+    key: [path, line_number, column],
+    repr: [path, line_number, column, line],
+);
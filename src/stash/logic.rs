@@ -0,0 +1,229 @@
+use crate::commits::logic::diff_entries;
+use crate::commits::DiffEntry;
+use crate::index::{index_conflicts, refresh_index, Conflict};
+use crate::repo::Repo;
+use crate::utils::git_err_to_py_err;
+use git2::{ErrorCode, Oid, Repository, StashApplyOptions, StashFlags};
+use pyo3::exceptions::{PyIndexError, PyValueError};
+use pyo3::prelude::*;
+
+use super::model::Stash;
+
+/// Commit id of `stash@{index}`.
+fn stash_id(repo: &mut Repository, index: usize) -> PyResult<Oid> {
+    let mut found = None;
+    repo.stash_foreach(|i, _, oid| {
+        if i == index {
+            found = Some(*oid);
+            false
+        } else {
+            true
+        }
+    })
+    .map_err(git_err_to_py_err)?;
+    found.ok_or_else(|| PyIndexError::new_err(format!("No stash entry stash@{{{}}}", index)))
+}
+
+/// Apply `stash@{index}`, returning the conflicts left in the index.
+///
+/// Applying over local changes to the same files is refused by libgit2
+/// before anything is touched; that is reported as an error rather than as
+/// conflicts.
+fn apply_stash(
+    repo: &mut Repository,
+    index: usize,
+    reinstate_index: bool,
+) -> PyResult<Vec<Conflict>> {
+    stash_id(repo, index)?;
+    refresh_index(repo).map_err(git_err_to_py_err)?;
+    let mut options = StashApplyOptions::new();
+    if reinstate_index {
+        options.reinstantiate_index();
+    }
+    match repo.stash_apply(index, Some(&mut options)) {
+        Ok(()) => {}
+        Err(e) if e.code() == ErrorCode::Conflict => {
+            return Err(PyValueError::new_err(format!(
+                "Stash would overwrite local changes: {}",
+                e.message()
+            )))
+        }
+        Err(e) if e.code() == ErrorCode::MergeConflict => {}
+        Err(e) => return Err(git_err_to_py_err(e)),
+    }
+    let repo_index = repo.index().map_err(git_err_to_py_err)?;
+    index_conflicts(&repo_index).map_err(git_err_to_py_err)
+}
+
+#[pymethods]
+impl Repo {
+    /// Stash local changes, like `git stash push`.
+    ///
+    /// Returns the id of the stash commit, or `None` when there was nothing
+    /// to stash.
+    #[pyo3(signature = (message=None, include_untracked=false, keep_index=false))]
+    fn stash_save(
+        &mut self,
+        message: Option<&str>,
+        include_untracked: bool,
+        keep_index: bool,
+    ) -> PyResult<Option<String>> {
+        let signature = self.inner.signature().map_err(git_err_to_py_err)?;
+        let mut flags = StashFlags::DEFAULT;
+        if include_untracked {
+            flags |= StashFlags::INCLUDE_UNTRACKED;
+        }
+        if keep_index {
+            flags |= StashFlags::KEEP_INDEX;
+        }
+        match self.inner.stash_save2(&signature, message, Some(flags)) {
+            Ok(oid) => Ok(Some(oid.to_string())),
+            Err(e) if e.code() == ErrorCode::NotFound => Ok(None),
+            Err(e) => Err(git_err_to_py_err(e)),
+        }
+    }
+
+    /// The stash entries, most recent first.
+    fn stash_list(&mut self) -> PyResult<Vec<Stash>> {
+        let mut entries = Vec::new();
+        self.inner
+            .stash_foreach(|index, message, oid| {
+                entries.push((index, message.to_string(), *oid));
+                true
+            })
+            .map_err(git_err_to_py_err)?;
+
+        entries
+            .into_iter()
+            .map(|(index, message, oid)| {
+                let commit = self.inner.find_commit(oid).map_err(git_err_to_py_err)?;
+                Ok(Stash {
+                    index,
+                    message,
+                    commit_id: oid.to_string(),
+                    time: commit.time().seconds(),
+                    offset_minutes: commit.time().offset_minutes(),
+                })
+            })
+            .collect()
+    }
+
+    /// Apply a stash entry without removing it. Returns the paths left
+    /// conflicted, which is empty when the stash applied cleanly.
+    #[pyo3(signature = (index=0, reinstate_index=false))]
+    fn stash_apply(&mut self, index: usize, reinstate_index: bool) -> PyResult<Vec<Conflict>> {
+        apply_stash(&mut self.inner, index, reinstate_index)
+    }
+
+    /// Apply a stash entry and drop it if it applied without conflicts.
+    #[pyo3(signature = (index=0, reinstate_index=false))]
+    fn stash_pop(&mut self, index: usize, reinstate_index: bool) -> PyResult<Vec<Conflict>> {
+        let conflicts = apply_stash(&mut self.inner, index, reinstate_index)?;
+        if conflicts.is_empty() {
+            self.inner.stash_drop(index).map_err(git_err_to_py_err)?;
+        }
+        Ok(conflicts)
+    }
+
+    /// Remove a stash entry.
+    #[pyo3(signature = (index=0))]
+    fn stash_drop(&mut self, index: usize) -> PyResult<()> {
+        stash_id(&mut self.inner, index)?;
+        self.inner.stash_drop(index).map_err(git_err_to_py_err)
+    }
+
+    /// Per-file line statistics of a stash entry against the commit it was
+    /// based on, like `git stash show --numstat`.
+    #[pyo3(signature = (index=0))]
+    fn stash_show(&mut self, index: usize) -> PyResult<Vec<DiffEntry>> {
+        let oid = stash_id(&mut self.inner, index)?;
+        let stash = self.inner.find_commit(oid).map_err(git_err_to_py_err)?;
+        let base = stash.parent(0).map_err(git_err_to_py_err)?;
+        let diff = self
+            .inner
+            .diff_tree_to_tree(
+                Some(&base.tree().map_err(git_err_to_py_err)?),
+                Some(&stash.tree().map_err(git_err_to_py_err)?),
+                None,
+            )
+            .map_err(git_err_to_py_err)?;
+        diff_entries(&diff).map_err(git_err_to_py_err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::TestRepo;
+
+    #[test]
+    fn save_and_pop_round_trip() {
+        let mut t = TestRepo::new();
+        t.commit("file.txt", "one\n", "initial");
+        t.write("file.txt", "two\n");
+
+        let id = t.repo.stash_save(Some("wip"), false, false).unwrap();
+        assert!(id.is_some());
+        assert_eq!(t.read("file.txt"), "one\n");
+        assert!(t.is_clean());
+        let stashes = t.repo.stash_list().unwrap();
+        assert_eq!(stashes.len(), 1);
+        assert_eq!(stashes[0].commit_id, id.unwrap());
+        assert!(stashes[0].message.contains("wip"));
+
+        let conflicts = t.repo.stash_pop(0, false).unwrap();
+        assert!(conflicts.is_empty());
+        assert_eq!(t.read("file.txt"), "two\n");
+        assert!(t.repo.stash_list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn save_without_changes_returns_none() {
+        let mut t = TestRepo::new();
+        t.commit("file.txt", "one\n", "initial");
+        assert_eq!(t.repo.stash_save(None, false, false).unwrap(), None);
+    }
+
+    #[test]
+    fn include_untracked_stashes_new_files() {
+        let mut t = TestRepo::new();
+        t.commit("file.txt", "one\n", "initial");
+        t.write("new.txt", "new\n");
+
+        assert_eq!(t.repo.stash_save(None, false, false).unwrap(), None);
+        assert!(t.repo.stash_save(None, true, false).unwrap().is_some());
+        assert!(!t.path().join("new.txt").exists());
+        t.repo.stash_apply(0, false).unwrap();
+        assert_eq!(t.read("new.txt"), "new\n");
+    }
+
+    #[test]
+    fn pop_with_conflicts_keeps_the_entry() {
+        let mut t = TestRepo::new();
+        t.commit("file.txt", "one\n", "initial");
+        t.write("file.txt", "stashed\n");
+        t.repo.stash_save(None, false, false).unwrap();
+        t.commit("file.txt", "committed\n", "diverge");
+
+        let conflicts = t.repo.stash_pop(0, false).unwrap();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].path, "file.txt");
+        assert_eq!(t.repo.stash_list().unwrap().len(), 1);
+
+        t.repo.stash_drop(0).unwrap();
+        assert!(t.repo.stash_list().unwrap().is_empty());
+        assert!(t.repo.stash_drop(0).is_err());
+    }
+
+    #[test]
+    fn show_lists_stashed_files() {
+        let mut t = TestRepo::new();
+        t.commit("file.txt", "one\n", "initial");
+        t.write("file.txt", "one\ntwo\n");
+        t.repo.stash_save(None, false, false).unwrap();
+
+        let entries = t.repo.stash_show(0).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path, "file.txt");
+        assert_eq!((entries[0].additions, entries[0].deletions), (1, 0));
+    }
+}
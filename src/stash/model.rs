@@ -0,0 +1,34 @@
+use crate::utils::{py_model, to_py_datetime};
+use pyo3::prelude::*;
+
+/// An entry of the stash list; `index` 0 is the most recent (`stash@{0}`).
+#[pyclass(module = "rustygit")]
+#[derive(Clone, Debug)]
+pub struct Stash {
+    #[pyo3(get)]
+    pub index: usize,
+    #[pyo3(get)]
+    pub message: String,
+    #[pyo3(get)]
+    pub commit_id: String,
+    #[pyo3(get)]
+    pub time: i64,
+    #[pyo3(get)]
+    pub offset_minutes: i32,
+}
+
+py_model!(
+    Stash,
+    fields: [index, message, commit_id, time, offset_minutes],
+    key: [index, commit_id],
+    repr: [index, message, commit_id],
+);
+
+#[pymethods]
+impl Stash {
+    /// Time the stash was saved as a timezone-aware `datetime`
+    #[getter]
+    fn datetime(&self, py: Python) -> PyResult<PyObject> {
+        to_py_datetime(py, self.time, self.offset_minutes)
+    }
+}
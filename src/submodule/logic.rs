@@ -0,0 +1,333 @@
+use crate::repo::Repo;
+use crate::utils::git_err_to_py_err;
+use git2::{ErrorCode, Repository, SubmoduleIgnore, SubmoduleStatus};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use super::model::Submodule;
+
+const STATUS_FLAGS: [(SubmoduleStatus, &str); 14] = [
+    (SubmoduleStatus::IN_HEAD, "in_head"),
+    (SubmoduleStatus::IN_INDEX, "in_index"),
+    (SubmoduleStatus::IN_CONFIG, "in_config"),
+    (SubmoduleStatus::IN_WD, "in_wd"),
+    (SubmoduleStatus::INDEX_ADDED, "index_added"),
+    (SubmoduleStatus::INDEX_DELETED, "index_deleted"),
+    (SubmoduleStatus::INDEX_MODIFIED, "index_modified"),
+    (SubmoduleStatus::WD_UNINITIALIZED, "wd_uninitialized"),
+    (SubmoduleStatus::WD_ADDED, "wd_added"),
+    (SubmoduleStatus::WD_DELETED, "wd_deleted"),
+    (SubmoduleStatus::WD_MODIFIED, "wd_modified"),
+    (SubmoduleStatus::WD_INDEX_MODIFIED, "wd_index_modified"),
+    (SubmoduleStatus::WD_WD_MODIFIED, "wd_wd_modified"),
+    (SubmoduleStatus::WD_UNTRACKED, "wd_untracked"),
+];
+
+fn to_submodule(repo: &Repository, submodule: &git2::Submodule) -> Result<Submodule, git2::Error> {
+    let name = submodule.name().unwrap_or_default().to_string();
+    let status = repo.submodule_status(&name, SubmoduleIgnore::Unspecified)?;
+    Ok(Submodule {
+        path: submodule.path().to_string_lossy().into_owned(),
+        url: submodule.url().map(str::to_string),
+        branch: submodule.branch().map(str::to_string),
+        head_id: submodule.head_id().map(|oid| oid.to_string()),
+        index_id: submodule.index_id().map(|oid| oid.to_string()),
+        workdir_id: submodule.workdir_id().map(|oid| oid.to_string()),
+        status: STATUS_FLAGS
+            .iter()
+            .filter(|(flag, _)| status.contains(*flag))
+            .map(|(_, name)| name.to_string())
+            .collect(),
+        name,
+    })
+}
+
+/// The submodules named (by name or path) in `names`, or all of them.
+fn select_submodules<'r>(
+    repo: &'r Repository,
+    names: Option<Vec<String>>,
+) -> PyResult<Vec<git2::Submodule<'r>>> {
+    match names {
+        Some(names) => names
+            .iter()
+            .map(|name| find_submodule(repo, name))
+            .collect(),
+        None => repo.submodules().map_err(git_err_to_py_err),
+    }
+}
+
+fn find_submodule<'r>(repo: &'r Repository, name: &str) -> PyResult<git2::Submodule<'r>> {
+    repo.find_submodule(name).map_err(|e| match e.code() {
+        ErrorCode::NotFound => PyValueError::new_err(format!("No submodule '{}'", name)),
+        _ => git_err_to_py_err(e),
+    })
+}
+
+/// Clone or check out `submodules` at the commits recorded in the index,
+/// then do the same inside each of them when `recursive`.
+fn update_submodules(
+    submodules: Vec<git2::Submodule>,
+    init: bool,
+    recursive: bool,
+) -> Result<(), git2::Error> {
+    for mut submodule in submodules {
+        submodule.update(init, None)?;
+        if recursive {
+            if let Ok(nested) = submodule.open() {
+                update_submodules(nested.submodules()?, init, recursive)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[pymethods]
+impl Repo {
+    /// The repository's submodules with their recorded and checked-out
+    /// commits.
+    fn submodules(&self) -> PyResult<Vec<Submodule>> {
+        let submodules = self.inner.submodules().map_err(git_err_to_py_err)?;
+        submodules
+            .iter()
+            .map(|submodule| to_submodule(&self.inner, submodule).map_err(git_err_to_py_err))
+            .collect()
+    }
+
+    /// Copy submodule URLs from `.gitmodules` into `.git/config`, like
+    /// `git submodule init`. `overwrite` replaces URLs already configured.
+    #[pyo3(signature = (paths=None, overwrite=false))]
+    fn submodule_init(&self, paths: Option<Vec<String>>, overwrite: bool) -> PyResult<()> {
+        for mut submodule in select_submodules(&self.inner, paths)? {
+            submodule.init(overwrite).map_err(git_err_to_py_err)?;
+        }
+        Ok(())
+    }
+
+    /// Clone missing submodules and check out the commits recorded in the
+    /// index, like `git submodule update [--init] [--recursive]`.
+    #[pyo3(signature = (paths=None, init=false, recursive=false))]
+    fn submodule_update(
+        &self,
+        paths: Option<Vec<String>>,
+        init: bool,
+        recursive: bool,
+    ) -> PyResult<()> {
+        let submodules = select_submodules(&self.inner, paths)?;
+        update_submodules(submodules, init, recursive).map_err(git_err_to_py_err)
+    }
+
+    /// Copy submodule URLs from `.gitmodules` into the configs of the
+    /// superproject and of each checked-out submodule, like
+    /// `git submodule sync`.
+    #[pyo3(signature = (paths=None))]
+    fn submodule_sync(&self, paths: Option<Vec<String>>) -> PyResult<()> {
+        for mut submodule in select_submodules(&self.inner, paths)? {
+            submodule.sync().map_err(git_err_to_py_err)?;
+        }
+        Ok(())
+    }
+
+    /// Open a checked-out submodule, by name or path, as a `Repo`.
+    fn open_submodule(&self, name: &str) -> PyResult<Repo> {
+        let submodule = find_submodule(&self.inner, name)?;
+        let repo = submodule.open().map_err(git_err_to_py_err)?;
+        let path = self
+            .inner
+            .workdir()
+            .map(|workdir| workdir.join(submodule.path()))
+            .unwrap_or_else(|| submodule.path().to_path_buf());
+        Ok(Repo::from_repository(
+            repo,
+            path.to_string_lossy().into_owned(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestRepo;
+    use git2::Oid;
+    use std::path::Path;
+
+    /// Commit whatever is staged in `repo`'s index on top of `HEAD`.
+    fn commit_index(repo: &Repository, message: &str) -> Oid {
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let signature = repo.signature().unwrap();
+        let parent = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &[&parent],
+        )
+        .unwrap()
+    }
+
+    /// Clone `url` into `path` and stage it as a submodule.
+    fn add_submodule(repo: &Repository, url: &str, path: &str) {
+        let mut submodule = repo.submodule(url, Path::new(path), true).unwrap();
+        submodule.clone(None).unwrap();
+        submodule.add_finalize().unwrap();
+    }
+
+    /// A superproject with `lib` checked out at `vendor/lib`.
+    fn with_submodule() -> (TestRepo, TestRepo) {
+        let lib = TestRepo::new();
+        lib.commit("lib.txt", "v1\n", "lib v1");
+        let t = TestRepo::new();
+        t.commit("README.md", "super\n", "init");
+        add_submodule(t.git(), lib.path().to_str().unwrap(), "vendor/lib");
+        commit_index(t.git(), "add lib");
+        (t, lib)
+    }
+
+    fn has(submodule: &Submodule, flag: &str) -> bool {
+        submodule.status.iter().any(|status| status == flag)
+    }
+
+    #[test]
+    fn submodules_lists_recorded_and_checked_out_commits() {
+        let (t, lib) = with_submodule();
+        let submodules = t.repo.submodules().unwrap();
+        assert_eq!(submodules.len(), 1);
+        let submodule = &submodules[0];
+        assert_eq!(submodule.name, "vendor/lib");
+        assert_eq!(submodule.path, "vendor/lib");
+        assert_eq!(submodule.url.as_deref(), Some(lib.path().to_str().unwrap()));
+        assert_eq!(submodule.branch, None);
+        let recorded = Some(lib.head().to_string());
+        assert_eq!(submodule.head_id, recorded);
+        assert_eq!(submodule.index_id, recorded);
+        assert_eq!(submodule.workdir_id, recorded);
+        for flag in ["in_head", "in_index", "in_config", "in_wd"] {
+            assert!(has(submodule, flag), "{}", flag);
+        }
+        assert!(!has(submodule, "wd_uninitialized"));
+    }
+
+    #[test]
+    fn open_submodule_by_name_or_path() {
+        let (t, lib) = with_submodule();
+        let opened = t.repo.open_submodule("vendor/lib").unwrap();
+        assert_eq!(opened.inner.head().unwrap().target(), Some(lib.head()));
+        assert!(opened.inner.workdir().unwrap().ends_with("vendor/lib"));
+
+        let err = t.repo.open_submodule("missing").err().unwrap();
+        Python::with_gil(|py| assert!(err.is_instance_of::<PyValueError>(py)));
+        let err = t
+            .repo
+            .submodule_init(Some(vec!["missing".to_string()]), false)
+            .unwrap_err();
+        Python::with_gil(|py| assert!(err.is_instance_of::<PyValueError>(py)));
+    }
+
+    #[test]
+    fn init_and_update_a_fresh_clone() {
+        let (t, lib) = with_submodule();
+        let clone_path = t.sibling("clone");
+        let inner = Repository::clone(t.path().to_str().unwrap(), &clone_path).unwrap();
+        let clone = Repo::from_repository(inner, clone_path.to_string_lossy().into_owned());
+
+        let submodule = clone.submodules().unwrap().remove(0);
+        assert!(has(&submodule, "wd_uninitialized"));
+        assert_eq!(submodule.workdir_id, None);
+        assert_eq!(submodule.head_id, Some(lib.head().to_string()));
+
+        clone.submodule_init(None, false).unwrap();
+        let config = clone.inner.config().unwrap().snapshot().unwrap();
+        assert_eq!(
+            config.get_str("submodule.vendor/lib.url").unwrap(),
+            lib.path().to_str().unwrap()
+        );
+
+        clone.submodule_update(None, false, false).unwrap();
+        let submodule = clone.submodules().unwrap().remove(0);
+        assert!(!has(&submodule, "wd_uninitialized"));
+        assert_eq!(submodule.workdir_id, Some(lib.head().to_string()));
+        assert_eq!(
+            std::fs::read_to_string(clone_path.join("vendor/lib/lib.txt")).unwrap(),
+            "v1\n"
+        );
+    }
+
+    #[test]
+    fn update_checks_out_the_recorded_commit_again() {
+        let (t, lib) = with_submodule();
+        let recorded = lib.head();
+        let checkout = t.path().join("vendor/lib");
+        std::fs::write(checkout.join("lib.txt"), "local\n").unwrap();
+        let nested = Repository::open(&checkout).unwrap();
+        let mut config = nested.config().unwrap();
+        config.set_str("user.name", "Test User").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+        let mut index = nested.index().unwrap();
+        index.add_path(Path::new("lib.txt")).unwrap();
+        index.write().unwrap();
+        let moved = commit_index(&nested, "local change");
+
+        let submodule = t.repo.submodules().unwrap().remove(0);
+        assert_eq!(submodule.workdir_id, Some(moved.to_string()));
+        assert!(has(&submodule, "wd_modified"));
+
+        t.repo
+            .submodule_update(Some(vec!["vendor/lib".to_string()]), false, false)
+            .unwrap();
+        let submodule = t.repo.submodules().unwrap().remove(0);
+        assert_eq!(submodule.workdir_id, Some(recorded.to_string()));
+        assert_eq!(
+            std::fs::read_to_string(checkout.join("lib.txt")).unwrap(),
+            "v1\n"
+        );
+    }
+
+    #[test]
+    fn sync_copies_urls_from_gitmodules() {
+        let (t, _lib) = with_submodule();
+        let moved = t.sibling("moved-lib").to_string_lossy().into_owned();
+        let mut gitmodules = git2::Config::open(&t.path().join(".gitmodules")).unwrap();
+        gitmodules
+            .set_str("submodule.vendor/lib.url", &moved)
+            .unwrap();
+
+        t.repo.submodule_sync(None).unwrap();
+        let config = t.git().config().unwrap().snapshot().unwrap();
+        assert_eq!(config.get_str("submodule.vendor/lib.url").unwrap(), moved);
+        let nested = Repository::open(t.path().join("vendor/lib")).unwrap();
+        assert_eq!(
+            nested.find_remote("origin").unwrap().url(),
+            Some(moved.as_str())
+        );
+    }
+
+    #[test]
+    fn update_with_init_and_recursive_clones_nested_submodules() {
+        let inner = TestRepo::new();
+        inner.commit("inner.txt", "inner\n", "inner");
+        let lib = TestRepo::new();
+        lib.commit("lib.txt", "v1\n", "lib v1");
+        add_submodule(lib.git(), inner.path().to_str().unwrap(), "deps/inner");
+        commit_index(lib.git(), "add inner");
+        let t = TestRepo::new();
+        t.commit("README.md", "super\n", "init");
+        add_submodule(t.git(), lib.path().to_str().unwrap(), "vendor/lib");
+        commit_index(t.git(), "add lib");
+
+        let clone_path = t.sibling("clone");
+        let cloned = Repository::clone(t.path().to_str().unwrap(), &clone_path).unwrap();
+        let clone = Repo::from_repository(cloned, clone_path.to_string_lossy().into_owned());
+        clone.submodule_update(None, true, true).unwrap();
+
+        let nested = clone.open_submodule("vendor/lib").unwrap();
+        let submodule = nested.submodules().unwrap().remove(0);
+        assert_eq!(submodule.workdir_id, Some(inner.head().to_string()));
+        assert_eq!(
+            std::fs::read_to_string(clone_path.join("vendor/lib/deps/inner/inner.txt")).unwrap(),
+            "inner\n"
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use crate::utils::py_model;
+use pyo3::prelude::*;
+
+/// A submodule of the repository, as configured in `.gitmodules`.
+///
+/// `head_id` and `index_id` are the commits recorded for it in the
+/// superproject's `HEAD` and index; `workdir_id` is the commit checked out
+/// in its working directory, `None` when it isn't initialized.
+#[pyclass(module = "rustygit")]
+#[derive(Clone, Debug)]
+pub struct Submodule {
+    #[pyo3(get)]
+    pub name: String,
+    #[pyo3(get)]
+    pub path: String,
+    #[pyo3(get)]
+    pub url: Option<String>,
+    #[pyo3(get)]
+    pub branch: Option<String>,
+    #[pyo3(get)]
+    pub head_id: Option<String>,
+    #[pyo3(get)]
+    pub index_id: Option<String>,
+    #[pyo3(get)]
+    pub workdir_id: Option<String>,
+    /// libgit2 status flags, e.g. `in_head`, `wd_uninitialized` or
+    /// `wd_modified`
+    #[pyo3(get)]
+    pub status: Vec<String>,
+}
+
+py_model!(
+    Submodule,
+    fields: [name, path, url, branch, head_id, index_id, workdir_id, status],
+    key: [name, head_id, workdir_id],
+    repr: [name, path, head_id, workdir_id],
+);
+
+#[pymethods]
+impl Submodule {
+    /// Whether the submodule is cloned into its working directory
+    #[getter]
+    fn is_initialized(&self) -> bool {
+        !self.status.iter().any(|flag| flag == "wd_uninitialized")
+    }
+
+    /// Whether the checked-out commit differs from the one recorded in `HEAD`
+    #[getter]
+    fn is_out_of_date(&self) -> bool {
+        self.workdir_id.is_some() && self.workdir_id != self.head_id
+    }
+}
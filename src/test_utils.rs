@@ -0,0 +1,139 @@
+//! Fixtures for the unit tests: throwaway repositories with an identity
+//! configured, built and inspected through libgit2 directly.
+
+use crate::repo::Repo;
+use git2::{Oid, Repository};
+use pyo3::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+pub struct TestRepo {
+    pub repo: Repo,
+    dir: TempDir,
+}
+
+impl TestRepo {
+    /// An empty repository whose local config sets `user.name` and
+    /// `user.email`.
+    pub fn new() -> Self {
+        pyo3::prepare_freethreaded_python();
+        let dir = TempDir::new().unwrap();
+        let inner = Repository::init(dir.path().join("repo")).unwrap();
+        let mut config = inner.config().unwrap();
+        config.set_str("user.name", "Test User").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+        let path = dir.path().join("repo").to_string_lossy().into_owned();
+        TestRepo {
+            repo: Repo::from_repository(inner, path),
+            dir,
+        }
+    }
+
+    /// Another handle on the repository as a Python object, for the methods
+    /// that take a `PyRef<Repo>`.
+    pub fn py_repo(&self, py: Python) -> Py<Repo> {
+        let inner = Repository::open(self.path()).unwrap();
+        let path = self.path().to_string_lossy().into_owned();
+        Py::new(py, Repo::from_repository(inner, path)).unwrap()
+    }
+
+    pub fn git(&self) -> &Repository {
+        &self.repo.inner
+    }
+
+    /// The working directory.
+    pub fn path(&self) -> PathBuf {
+        self.dir.path().join("repo")
+    }
+
+    /// A path beside the working directory that is removed with it.
+    pub fn sibling(&self, name: &str) -> PathBuf {
+        self.dir.path().join(name)
+    }
+
+    pub fn write(&self, path: &str, content: &str) {
+        fs::write(self.path().join(path), content).unwrap();
+    }
+
+    pub fn read(&self, path: &str) -> String {
+        fs::read_to_string(self.path().join(path)).unwrap()
+    }
+
+    /// Write `path`, stage it and commit it on top of `HEAD`.
+    pub fn commit(&self, path: &str, content: &str, message: &str) -> Oid {
+        self.write(path, content);
+        let repo = self.git();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(path)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = repo.signature().unwrap();
+        let parent = repo.head().ok().map(|head| head.peel_to_commit().unwrap());
+        let parents: Vec<_> = parent.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .unwrap()
+    }
+
+    /// Create `branch` as a single root commit with an empty tree, without
+    /// checking it out.
+    pub fn orphan(&self, branch: &str) -> Oid {
+        let repo = self.git();
+        let signature = repo.signature().unwrap();
+        let tree = repo
+            .find_tree(repo.treebuilder(None).unwrap().write().unwrap())
+            .unwrap();
+        let refname = format!("refs/heads/{}", branch);
+        repo.commit(Some(&refname), &signature, &signature, branch, &tree, &[])
+            .unwrap()
+    }
+
+    pub fn head(&self) -> Oid {
+        self.git().head().unwrap().target().unwrap()
+    }
+
+    /// Full name of the branch `HEAD` is attached to, e.g. `refs/heads/main`.
+    pub fn head_name(&self) -> Option<String> {
+        let head = self.git().find_reference("HEAD").unwrap();
+        head.symbolic_target().map(str::to_string)
+    }
+
+    /// Create a branch at `target` and check it out.
+    pub fn checkout_new_branch(&self, name: &str, target: Oid) {
+        let repo = self.git();
+        let commit = repo.find_commit(target).unwrap();
+        repo.branch(name, &commit, false).unwrap();
+        self.checkout(&format!("refs/heads/{}", name));
+    }
+
+    /// Check out a branch by its full ref name.
+    pub fn checkout(&self, refname: &str) {
+        let repo = self.git();
+        let commit = repo
+            .find_reference(refname)
+            .unwrap()
+            .peel_to_commit()
+            .unwrap();
+        repo.checkout_tree(
+            commit.as_object(),
+            Some(git2::build::CheckoutBuilder::new().force()),
+        )
+        .unwrap();
+        repo.set_head(refname).unwrap();
+    }
+
+    /// Whether the working tree and index match `HEAD`, ignoring untracked
+    /// files.
+    pub fn is_clean(&self) -> bool {
+        let mut options = git2::StatusOptions::new();
+        options.include_untracked(false);
+        self.git().statuses(Some(&mut options)).unwrap().is_empty()
+    }
+}
@@ -0,0 +1,342 @@
+use crate::repo::Repo;
+use crate::utils::git_err_to_py_err;
+use git2::{ObjectType, Pathspec, PathspecFlags, Repository, Tree, TreeWalkMode, TreeWalkResult};
+use pyo3::exceptions::PyFileNotFoundError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use std::path::Path;
+
+use super::model::{BlobIter, PendingBlob, TreeEntry};
+
+/// List the entries of `tree`, prefixing paths with `base`.
+///
+/// When `recursive` is set, subtrees are descended into and only their
+/// contents are listed, like `git ls-tree -r`. Blob sizes are read from the
+/// object header without loading the content.
+pub fn tree_entries(
+    repo: &Repository,
+    tree: &Tree,
+    base: &str,
+    recursive: bool,
+) -> Result<Vec<TreeEntry>, git2::Error> {
+    let odb = repo.odb()?;
+    let prefix = if base.is_empty() {
+        String::new()
+    } else {
+        format!("{}/", base.trim_end_matches('/'))
+    };
+
+    let mut entries = Vec::new();
+    let mut failure = None;
+    let walked = tree.walk(TreeWalkMode::PreOrder, |root, entry| {
+        let kind = entry.kind();
+        if recursive && kind == Some(ObjectType::Tree) {
+            return TreeWalkResult::Ok;
+        }
+
+        let size = if kind == Some(ObjectType::Blob) {
+            match odb.read_header(entry.id()) {
+                Ok((size, _)) => Some(size),
+                Err(e) => {
+                    failure = Some(e);
+                    return TreeWalkResult::Abort;
+                }
+            }
+        } else {
+            None
+        };
+
+        entries.push(TreeEntry {
+            path: format!("{}{}{}", prefix, root, entry.name().unwrap_or("")),
+            mode: entry.filemode(),
+            kind: kind.map_or("unknown", |k| k.str()).to_string(),
+            id: entry.id().to_string(),
+            size,
+        });
+
+        if recursive {
+            TreeWalkResult::Ok
+        } else {
+            TreeWalkResult::Skip
+        }
+    });
+
+    if let Some(e) = failure {
+        return Err(e);
+    }
+    walked?;
+    Ok(entries)
+}
+
+/// Describe the entry at `path` inside `tree`.
+pub fn entry_at(repo: &Repository, tree: &Tree, path: &str) -> Result<TreeEntry, git2::Error> {
+    let entry = tree.get_path(Path::new(path))?;
+    let kind = entry.kind();
+    let size = if kind == Some(ObjectType::Blob) {
+        Some(repo.odb()?.read_header(entry.id())?.0)
+    } else {
+        None
+    };
+    Ok(TreeEntry {
+        path: path.trim_end_matches('/').to_string(),
+        mode: entry.filemode(),
+        kind: kind.map_or("unknown", |k| k.str()).to_string(),
+        id: entry.id().to_string(),
+        size,
+    })
+}
+
+/// Select the blobs in `tree` matching `pathspec` (all blobs when empty),
+/// leaving out those larger than `max_size` bytes.
+pub fn matching_blobs(
+    repo: &Repository,
+    tree: &Tree,
+    pathspec: &[String],
+    max_size: Option<usize>,
+) -> Result<Vec<PendingBlob>, git2::Error> {
+    let odb = repo.odb()?;
+    let pathspec = Pathspec::new(pathspec.iter())?;
+
+    let mut blobs = Vec::new();
+    let mut failure = None;
+    let walked = tree.walk(TreeWalkMode::PreOrder, |root, entry| {
+        if entry.kind() != Some(ObjectType::Blob) {
+            return TreeWalkResult::Ok;
+        }
+        let path = format!("{}{}", root, entry.name().unwrap_or(""));
+        if !pathspec.matches_path(Path::new(&path), PathspecFlags::DEFAULT) {
+            return TreeWalkResult::Ok;
+        }
+        if let Some(max_size) = max_size {
+            match odb.read_header(entry.id()) {
+                Ok((size, _)) if size > max_size => return TreeWalkResult::Ok,
+                Ok(_) => {}
+                Err(e) => {
+                    failure = Some(e);
+                    return TreeWalkResult::Abort;
+                }
+            }
+        }
+        blobs.push(PendingBlob {
+            path,
+            mode: entry.filemode(),
+            id: entry.id(),
+        });
+        TreeWalkResult::Ok
+    });
+
+    if let Some(e) = failure {
+        return Err(e);
+    }
+    walked?;
+    Ok(blobs)
+}
+
+fn not_found(rev: &str, path: &str) -> PyErr {
+    PyFileNotFoundError::new_err(format!("'{}' does not exist at {}", path, rev))
+}
+
+#[pymethods]
+impl Repo {
+    /// List a tree at `rev`, like `git ls-tree`.
+    ///
+    /// `path` selects a subdirectory to list, or a single entry to describe.
+    #[pyo3(signature = (rev="HEAD", path=None, recursive=false))]
+    fn ls_tree(&self, rev: &str, path: Option<&str>, recursive: bool) -> PyResult<Vec<TreeEntry>> {
+        let tree = self
+            .inner
+            .revparse_single(rev)
+            .and_then(|obj| obj.peel_to_tree())
+            .map_err(git_err_to_py_err)?;
+
+        let path = path.map(|p| p.trim_matches('/')).unwrap_or("");
+        if path.is_empty() {
+            return tree_entries(&self.inner, &tree, "", recursive).map_err(git_err_to_py_err);
+        }
+
+        let entry = entry_at(&self.inner, &tree, path).map_err(|_| not_found(rev, path))?;
+        if entry.kind != "tree" {
+            return Ok(vec![entry]);
+        }
+        let subtree = self
+            .inner
+            .find_tree(
+                tree.get_path(Path::new(path))
+                    .map_err(git_err_to_py_err)?
+                    .id(),
+            )
+            .map_err(git_err_to_py_err)?;
+        tree_entries(&self.inner, &subtree, path, recursive).map_err(git_err_to_py_err)
+    }
+
+    /// Content of the file at `path` as of `rev`, without checking it out.
+    fn read_file<'py>(&self, py: Python<'py>, rev: &str, path: &str) -> PyResult<&'py PyBytes> {
+        let tree = self
+            .inner
+            .revparse_single(rev)
+            .and_then(|obj| obj.peel_to_tree())
+            .map_err(git_err_to_py_err)?;
+        let entry = tree
+            .get_path(Path::new(path))
+            .map_err(|_| not_found(rev, path))?;
+        if entry.kind() != Some(ObjectType::Blob) {
+            return Err(not_found(rev, path));
+        }
+        let blob = self
+            .inner
+            .find_blob(entry.id())
+            .map_err(git_err_to_py_err)?;
+        Ok(PyBytes::new(py, blob.content()))
+    }
+
+    /// Whether a file (or symlink) exists at `path` as of `rev`.
+    fn file_exists(&self, rev: &str, path: &str) -> PyResult<bool> {
+        let tree = self
+            .inner
+            .revparse_single(rev)
+            .and_then(|obj| obj.peel_to_tree())
+            .map_err(git_err_to_py_err)?;
+        Ok(tree
+            .get_path(Path::new(path))
+            .is_ok_and(|entry| entry.kind() == Some(ObjectType::Blob)))
+    }
+
+    /// Stream the blobs at `rev` matching `pathspec`, reading each one only
+    /// when the iterator reaches it.
+    #[pyo3(signature = (rev="HEAD", pathspec=None, max_size=None, skip_binary=false))]
+    fn iter_blobs(
+        &self,
+        rev: &str,
+        pathspec: Option<Vec<String>>,
+        max_size: Option<usize>,
+        skip_binary: bool,
+    ) -> PyResult<BlobIter> {
+        let tree = self
+            .inner
+            .revparse_single(rev)
+            .and_then(|obj| obj.peel_to_tree())
+            .map_err(git_err_to_py_err)?;
+        let pending = matching_blobs(&self.inner, &tree, &pathspec.unwrap_or_default(), max_size)
+            .map_err(git_err_to_py_err)?;
+        let repo = Repository::open(self.inner.path()).map_err(git_err_to_py_err)?;
+        Ok(BlobIter {
+            repo,
+            pending: pending.into_iter(),
+            skip_binary,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestRepo;
+    use crate::tree::model::Blob;
+
+    fn repo_with_files() -> TestRepo {
+        let t = TestRepo::new();
+        std::fs::create_dir_all(t.path().join("src/nested")).unwrap();
+        t.commit("README.md", "readme\n", "add readme");
+        t.commit("src/lib.rs", "pub fn lib() {}\n", "add lib");
+        t.commit("src/nested/mod.rs", "// nested\n", "add nested");
+        t.commit("logo.png", "\u{0}PNG binary", "add logo");
+        t
+    }
+
+    fn paths(entries: &[TreeEntry]) -> Vec<&str> {
+        entries.iter().map(|entry| entry.path.as_str()).collect()
+    }
+
+    #[test]
+    fn ls_tree_lists_levels_and_single_entries() {
+        let t = repo_with_files();
+
+        let top = t.repo.ls_tree("HEAD", None, false).unwrap();
+        assert_eq!(paths(&top), vec!["README.md", "logo.png", "src"]);
+        assert_eq!(top[0].kind, "blob");
+        assert_eq!(top[0].size, Some(7));
+        assert_eq!(top[0].mode, 0o100644);
+        assert_eq!(top[2].kind, "tree");
+        assert_eq!(top[2].size, None);
+
+        let all = t.repo.ls_tree("HEAD", None, true).unwrap();
+        assert_eq!(
+            paths(&all),
+            vec!["README.md", "logo.png", "src/lib.rs", "src/nested/mod.rs"]
+        );
+        let src = t.repo.ls_tree("HEAD", Some("src/"), false).unwrap();
+        assert_eq!(paths(&src), vec!["src/lib.rs", "src/nested"]);
+        let file = t.repo.ls_tree("HEAD", Some("src/lib.rs"), false).unwrap();
+        assert_eq!(paths(&file), vec!["src/lib.rs"]);
+        let old = t.repo.ls_tree("HEAD~2", None, true).unwrap();
+        assert_eq!(paths(&old), vec!["README.md", "src/lib.rs"]);
+
+        let err = t.repo.ls_tree("HEAD", Some("missing"), false).unwrap_err();
+        Python::with_gil(|py| assert!(err.is_instance_of::<PyFileNotFoundError>(py)));
+    }
+
+    #[test]
+    fn read_file_and_file_exists_at_a_revision() {
+        let t = repo_with_files();
+        t.commit("README.md", "changed\n", "change readme");
+
+        Python::with_gil(|py| {
+            let old = t.repo.read_file(py, "HEAD~1", "README.md").unwrap();
+            assert_eq!(old.as_bytes(), b"readme\n");
+            let new = t.repo.read_file(py, "HEAD", "README.md").unwrap();
+            assert_eq!(new.as_bytes(), b"changed\n");
+            for path in ["src", "missing.txt"] {
+                let err = t.repo.read_file(py, "HEAD", path).unwrap_err();
+                assert!(err.is_instance_of::<PyFileNotFoundError>(py));
+            }
+        });
+        assert!(t.repo.file_exists("HEAD", "src/lib.rs").unwrap());
+        assert!(!t.repo.file_exists("HEAD", "src").unwrap());
+        assert!(!t.repo.file_exists("HEAD~4", "logo.png").unwrap());
+        assert!(t.repo.file_exists("missing-rev", "README.md").is_err());
+    }
+
+    #[test]
+    fn iter_blobs_filters_by_pathspec_size_and_binary() {
+        let t = repo_with_files();
+        let collect = |pathspec: Option<Vec<String>>, max_size, skip_binary| {
+            let blobs = t
+                .repo
+                .iter_blobs("HEAD", pathspec, max_size, skip_binary)
+                .unwrap();
+            Python::with_gil(|py| {
+                Py::new(py, blobs)
+                    .unwrap()
+                    .as_ref(py)
+                    .iter()
+                    .unwrap()
+                    .map(|blob| blob.unwrap().extract::<Blob>().unwrap())
+                    .collect::<Vec<_>>()
+            })
+        };
+        let names = |blobs: &[Blob]| {
+            blobs
+                .iter()
+                .map(|blob| blob.path.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let all = collect(None, None, false);
+        assert_eq!(
+            names(&all),
+            vec!["README.md", "logo.png", "src/lib.rs", "src/nested/mod.rs"]
+        );
+        assert!(all[1].is_binary);
+        assert_eq!(all[0].size, 7);
+        Python::with_gil(|py| assert_eq!(all[0].data.as_ref(py).as_bytes(), b"readme\n"));
+
+        assert_eq!(
+            names(&collect(Some(vec!["src/*.rs".to_string()]), None, false)),
+            vec!["src/lib.rs", "src/nested/mod.rs"]
+        );
+        assert_eq!(
+            names(&collect(None, Some(10), true)),
+            vec!["README.md", "src/nested/mod.rs"]
+        );
+    }
+}
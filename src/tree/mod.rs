@@ -0,0 +1,6 @@
+pub mod logic;
+pub mod model;
+
+pub use logic::{matching_blobs, tree_entries};
+
+pub use model::{Blob, BlobIter, TreeEntry};
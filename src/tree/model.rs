@@ -0,0 +1,90 @@
+use crate::utils::{git_err_to_py_err, py_model};
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+#[pyclass(module = "rustygit")]
+#[derive(Clone, Debug)]
+pub struct TreeEntry {
+    #[pyo3(get)]
+    pub path: String,
+    #[pyo3(get)]
+    pub mode: i32,
+    #[pyo3(get)]
+    pub kind: String,
+    #[pyo3(get)]
+    pub id: String,
+    #[pyo3(get)]
+    pub size: Option<usize>,
+}
+
+py_model!(
+    TreeEntry,
+    fields: [path, mode, kind, id, size],
+    key: [path, id],
+    repr: [path, kind, id],
+);
+
+/// A file's content at a revision, as yielded by `Repo.iter_blobs`.
+#[pyclass(module = "rustygit")]
+#[derive(Clone, Debug)]
+pub struct Blob {
+    #[pyo3(get)]
+    pub path: String,
+    #[pyo3(get)]
+    pub mode: i32,
+    #[pyo3(get)]
+    pub id: String,
+    #[pyo3(get)]
+    pub size: usize,
+    #[pyo3(get)]
+    pub is_binary: bool,
+    #[pyo3(get)]
+    pub data: Py<PyBytes>,
+}
+
+py_model!(
+    Blob,
+    fields: [path, mode, id, size, is_binary, data],
+    key: [path, id],
+    repr: [path, id, size],
+);
+
+/// A blob selected by a tree walk, read on demand.
+pub struct PendingBlob {
+    pub path: String,
+    pub mode: i32,
+    pub id: git2::Oid,
+}
+
+/// Iterator reading the blobs selected by `Repo.iter_blobs` one at a time.
+#[pyclass(unsendable, module = "rustygit")]
+pub struct BlobIter {
+    pub repo: git2::Repository,
+    pub pending: std::vec::IntoIter<PendingBlob>,
+    pub skip_binary: bool,
+}
+
+#[pymethods]
+impl BlobIter {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python<'_>) -> PyResult<Option<Blob>> {
+        for pending in self.pending.by_ref() {
+            let blob = self.repo.find_blob(pending.id).map_err(git_err_to_py_err)?;
+            if self.skip_binary && blob.is_binary() {
+                continue;
+            }
+            return Ok(Some(Blob {
+                path: pending.path,
+                mode: pending.mode,
+                id: pending.id.to_string(),
+                size: blob.size(),
+                is_binary: blob.is_binary(),
+                data: PyBytes::new(py, blob.content()).into(),
+            }));
+        }
+        Ok(None)
+    }
+}
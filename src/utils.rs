@@ -1,5 +1,6 @@
 use pyo3::exceptions::{PyIOError, PyValueError};
 use pyo3::prelude::*;
+use pyo3::types::IntoPyDict;
 use std::path::Path;
 
 /// Converts a git2 error to a Python error
@@ -18,10 +19,7 @@ pub fn is_git_repo(path: &str) -> bool {
         return false;
     }
 
-    match git2::Repository::open(path) {
-        Ok(_) => true,
-        Err(_) => false,
-    }
+    git2::Repository::open(path).is_ok()
 }
 
 /// Extract username and email from a git signature
@@ -31,3 +29,166 @@ pub fn signature_to_user_info(sig: git2::Signature) -> (Option<String>, Option<S
 
     (name, email)
 }
+
+/// Load the mailmap used to canonicalize identities: the supplied mailmap text
+/// if any, otherwise the repository's `.mailmap` plus `mailmap.file`/`mailmap.blob`
+pub fn load_mailmap(
+    repo: &git2::Repository,
+    mailmap: Option<&str>,
+) -> Result<git2::Mailmap, git2::Error> {
+    match mailmap {
+        Some(text) => git2::Mailmap::from_buffer(text),
+        None => repo.mailmap(),
+    }
+}
+
+/// Resolve a signature through a mailmap, falling back to the raw name and email
+pub fn mailmapped_identity(mailmap: &git2::Mailmap, sig: &git2::Signature) -> (String, String) {
+    match mailmap.resolve_signature(sig) {
+        Ok(resolved) => (
+            resolved.name().unwrap_or("").to_string(),
+            resolved.email().unwrap_or("").to_string(),
+        ),
+        Err(_) => (
+            sig.name().unwrap_or("").to_string(),
+            sig.email().unwrap_or("").to_string(),
+        ),
+    }
+}
+
+/// Convert epoch seconds and a UTC offset in minutes to a timezone-aware Python `datetime`
+pub fn to_py_datetime(py: Python, seconds: i64, offset_minutes: i32) -> PyResult<PyObject> {
+    let datetime = py.import("datetime")?;
+    let offset = datetime
+        .getattr("timedelta")?
+        .call((), Some([("minutes", offset_minutes)].into_py_dict(py)))?;
+    let tz = datetime.getattr("timezone")?.call1((offset,))?;
+    let value = datetime
+        .getattr("datetime")?
+        .call_method1("fromtimestamp", (seconds, tz))?;
+    Ok(value.to_object(py))
+}
+
+/// Implement the Python data-model protocols for a `#[pyclass]` model:
+/// `__repr__` over the `repr` fields, `__eq__` over every field, `__hash__`
+/// over the `key` fields, `to_dict`/`from_dict`, and pickling via
+/// `__reduce__`. Every field type must round-trip through Python.
+///
+/// Without `key` the model is unhashable, as Python makes any class that
+/// defines `__eq__` but not `__hash__`; use that for containers of results.
+macro_rules! py_model {
+    (
+        $ty:ident,
+        fields: [$($field:ident),* $(,)?],
+        key: [$($key:ident),* $(,)?],
+        repr: [$($shown:ident),* $(,)?] $(,)?
+    ) => {
+        $crate::utils::py_model!($ty, fields: [$($field),*], repr: [$($shown),*]);
+
+        #[pyo3::pymethods]
+        impl $ty {
+            fn __hash__(&self, py: pyo3::Python) -> pyo3::PyResult<isize> {
+                let key: Vec<pyo3::PyObject> =
+                    vec![$(pyo3::IntoPy::<pyo3::PyObject>::into_py(self.$key.clone(), py)),*];
+                pyo3::types::PyTuple::new(py, key).hash()
+            }
+        }
+    };
+    (
+        $ty:ident,
+        fields: [$($field:ident),* $(,)?],
+        repr: [$($shown:ident),* $(,)?] $(,)?
+    ) => {
+        #[pyo3::pymethods]
+        impl $ty {
+            fn __repr__(&self, py: pyo3::Python) -> pyo3::PyResult<String> {
+                let parts: Vec<String> = vec![$(
+                    format!(
+                        "{}={}",
+                        stringify!($shown).trim_start_matches("r#"),
+                        pyo3::IntoPy::<pyo3::PyObject>::into_py(self.$shown.clone(), py)
+                            .as_ref(py)
+                            .repr()?
+                    )
+                ),*];
+                Ok(format!("{}({})", stringify!($ty), parts.join(", ")))
+            }
+
+            fn __eq__(
+                &self,
+                py: pyo3::Python,
+                other: &pyo3::PyAny,
+            ) -> pyo3::PyResult<pyo3::PyObject> {
+                match other.extract::<pyo3::PyRef<$ty>>() {
+                    Ok(other) => {
+                        let equal = self.to_dict(py)?.as_ref(py).eq(other.to_dict(py)?)?;
+                        Ok(pyo3::IntoPy::into_py(equal, py))
+                    }
+                    Err(_) => Ok(py.NotImplemented()),
+                }
+            }
+
+            /// Fields as a plain dictionary, e.g. for `pandas.DataFrame`
+            fn to_dict(&self, py: pyo3::Python) -> pyo3::PyResult<pyo3::PyObject> {
+                let dict = pyo3::types::PyDict::new(py);
+                $(
+                    dict.set_item(
+                        stringify!($field).trim_start_matches("r#"),
+                        pyo3::IntoPy::<pyo3::PyObject>::into_py(self.$field.clone(), py),
+                    )?;
+                )*
+                Ok(dict.into())
+            }
+
+            /// Rebuild an instance from the output of `to_dict`
+            #[classmethod]
+            fn from_dict(
+                _cls: &pyo3::types::PyType,
+                dict: &pyo3::types::PyDict,
+            ) -> pyo3::PyResult<Self> {
+                Ok($ty {
+                    $(
+                        $field: dict
+                            .get_item(stringify!($field).trim_start_matches("r#"))?
+                            .ok_or_else(|| {
+                                pyo3::exceptions::PyKeyError::new_err(
+                                    stringify!($field).trim_start_matches("r#"),
+                                )
+                            })?
+                            .extract()?,
+                    )*
+                })
+            }
+
+            fn __reduce__(
+                slf: &pyo3::PyCell<Self>,
+                py: pyo3::Python,
+            ) -> pyo3::PyResult<(pyo3::PyObject, (pyo3::PyObject,))> {
+                let from_dict = slf.get_type().getattr("from_dict")?.into();
+                Ok((from_dict, (slf.borrow().to_dict(py)?,)))
+            }
+        }
+    };
+}
+
+pub(crate) use py_model;
+
+/// Collect the commits reachable from `range`: a single revision, an `A..B`
+/// range, or `HEAD` when `None`
+pub fn walk_revisions(
+    repo: &git2::Repository,
+    range: Option<&str>,
+) -> Result<Vec<git2::Oid>, git2::Error> {
+    let mut revwalk = repo.revwalk()?;
+    match range {
+        Some(range) if range.contains("..") => revwalk.push_range(range)?,
+        Some(rev) => revwalk.push(resolve_commit(repo, rev)?)?,
+        None => revwalk.push_head()?,
+    }
+    revwalk.collect()
+}
+
+/// Resolve any revspec to the id of the commit it points at
+pub fn resolve_commit(repo: &git2::Repository, rev: &str) -> Result<git2::Oid, git2::Error> {
+    Ok(repo.revparse_single(rev)?.peel_to_commit()?.id())
+}
@@ -0,0 +1,289 @@
+use crate::repo::Repo;
+use crate::utils::{git_err_to_py_err, resolve_commit};
+use git2::{
+    Branch, BranchType, ErrorCode, Repository, WorktreeAddOptions, WorktreeLockStatus,
+    WorktreePruneOptions,
+};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::path::Path;
+
+use super::model::Worktree;
+
+fn to_worktree(worktree: &git2::Worktree) -> Result<Worktree, git2::Error> {
+    let (locked, lock_reason) = match worktree.is_locked()? {
+        WorktreeLockStatus::Unlocked => (false, None),
+        WorktreeLockStatus::Locked(reason) => (true, reason.filter(|r| !r.is_empty())),
+    };
+    Ok(Worktree {
+        name: worktree.name().unwrap_or_default().to_string(),
+        path: worktree.path().to_string_lossy().into_owned(),
+        locked,
+        lock_reason,
+        is_valid: worktree.validate().is_ok(),
+    })
+}
+
+fn find_worktree(repo: &Repository, name: &str) -> PyResult<git2::Worktree> {
+    repo.find_worktree(name).map_err(|e| match e.code() {
+        ErrorCode::NotFound => PyValueError::new_err(format!("No worktree '{}'", name)),
+        _ => git_err_to_py_err(e),
+    })
+}
+
+#[pymethods]
+impl Repo {
+    /// The repository's linked worktrees (the main worktree is not listed).
+    fn worktrees(&self) -> PyResult<Vec<Worktree>> {
+        let names = self.inner.worktrees().map_err(git_err_to_py_err)?;
+        names
+            .iter()
+            .flatten()
+            .map(|name| {
+                let worktree = find_worktree(&self.inner, name)?;
+                to_worktree(&worktree).map_err(git_err_to_py_err)
+            })
+            .collect()
+    }
+
+    /// Create a linked worktree at `path`, like `git worktree add`.
+    ///
+    /// `ref` names a local branch to check out. When it is `None`, or a
+    /// revision that isn't a local branch, a new branch called `name` is
+    /// created at `HEAD` or at that revision.
+    #[pyo3(signature = (name, path, r#ref=None, lock=false))]
+    fn add_worktree(
+        &self,
+        name: &str,
+        path: &str,
+        r#ref: Option<&str>,
+        lock: bool,
+    ) -> PyResult<Worktree> {
+        let existing = match r#ref {
+            Some(rev) if Branch::name_is_valid(rev).map_err(git_err_to_py_err)? => {
+                match self.inner.find_branch(rev, BranchType::Local) {
+                    Ok(branch) => Some(branch),
+                    Err(e) if e.code() == ErrorCode::NotFound => None,
+                    Err(e) => return Err(git_err_to_py_err(e)),
+                }
+            }
+            _ => None,
+        };
+        let (branch, created) = match (existing, r#ref) {
+            (Some(branch), _) => (Some(branch), false),
+            (None, Some(rev)) => {
+                let oid = resolve_commit(&self.inner, rev).map_err(git_err_to_py_err)?;
+                let commit = self.inner.find_commit(oid).map_err(git_err_to_py_err)?;
+                let branch = self
+                    .inner
+                    .branch(name, &commit, false)
+                    .map_err(git_err_to_py_err)?;
+                (Some(branch), true)
+            }
+            (None, None) => (None, false),
+        };
+
+        let mut options = WorktreeAddOptions::new();
+        options.lock(lock);
+        let reference = branch.map(|branch| branch.into_reference());
+        options.reference(reference.as_ref());
+        let worktree = match self.inner.worktree(name, Path::new(path), Some(&options)) {
+            Ok(worktree) => worktree,
+            Err(e) => {
+                // Don't leave behind the branch made for the failed worktree.
+                if let Some(mut reference) = reference.filter(|_| created) {
+                    let _ = reference.delete();
+                }
+                return Err(git_err_to_py_err(e));
+            }
+        };
+        to_worktree(&worktree).map_err(git_err_to_py_err)
+    }
+
+    /// Remove a worktree's administrative files, like `git worktree prune`.
+    ///
+    /// Only worktrees whose directory is gone are pruned unless `force` is
+    /// set, which also prunes valid and locked ones. `working_tree` deletes
+    /// the worktree's directory as well.
+    #[pyo3(signature = (name, working_tree=true, force=false))]
+    fn prune_worktree(&self, name: &str, working_tree: bool, force: bool) -> PyResult<()> {
+        let worktree = find_worktree(&self.inner, name)?;
+        let mut options = WorktreePruneOptions::new();
+        options.working_tree(working_tree);
+        if force {
+            options.valid(true).locked(true);
+        }
+        worktree
+            .prune(Some(&mut options))
+            .map_err(git_err_to_py_err)
+    }
+
+    /// Lock a worktree so it isn't pruned, optionally recording why.
+    #[pyo3(signature = (name, reason=None))]
+    fn lock_worktree(&self, name: &str, reason: Option<&str>) -> PyResult<()> {
+        let worktree = find_worktree(&self.inner, name)?;
+        worktree.lock(reason).map_err(git_err_to_py_err)
+    }
+
+    /// Unlock a worktree locked by `lock_worktree` or `git worktree lock`.
+    fn unlock_worktree(&self, name: &str) -> PyResult<()> {
+        let worktree = find_worktree(&self.inner, name)?;
+        worktree.unlock().map_err(git_err_to_py_err)
+    }
+
+    /// Open a linked worktree as a `Repo`.
+    fn open_worktree(&self, name: &str) -> PyResult<Repo> {
+        let worktree = find_worktree(&self.inner, name)?;
+        let repo = Repository::open_from_worktree(&worktree).map_err(git_err_to_py_err)?;
+        Ok(Repo::from_repository(
+            repo,
+            worktree.path().to_string_lossy().into_owned(),
+        ))
+    }
+
+    /// Whether this repository is a linked worktree of another.
+    fn is_worktree(&self) -> bool {
+        self.inner.is_worktree()
+    }
+
+    /// The git directory shared by all worktrees, e.g. the main checkout's
+    /// `.git`.
+    fn common_dir(&self) -> String {
+        self.inner.commondir().to_string_lossy().into_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::TestRepo;
+    use git2::BranchType;
+    use std::fs;
+
+    fn branch_target(t: &TestRepo, name: &str) -> Option<git2::Oid> {
+        t.git()
+            .find_branch(name, BranchType::Local)
+            .ok()
+            .and_then(|branch| branch.get().target())
+    }
+
+    #[test]
+    fn add_creates_a_branch_at_head_or_revision() {
+        let t = TestRepo::new();
+        let first = t.commit("file.txt", "one\n", "first");
+        let second = t.commit("file.txt", "two\n", "second");
+
+        let path = t.sibling("at-head");
+        let worktree = t
+            .repo
+            .add_worktree("at-head", path.to_str().unwrap(), None, false)
+            .unwrap();
+        assert_eq!(worktree.name, "at-head");
+        assert!(worktree.is_valid);
+        assert_eq!(branch_target(&t, "at-head"), Some(second));
+        assert_eq!(fs::read_to_string(path.join("file.txt")).unwrap(), "two\n");
+
+        let path = t.sibling("at-rev");
+        t.repo
+            .add_worktree("at-rev", path.to_str().unwrap(), Some("HEAD~1"), false)
+            .unwrap();
+        assert_eq!(branch_target(&t, "at-rev"), Some(first));
+        assert_eq!(fs::read_to_string(path.join("file.txt")).unwrap(), "one\n");
+
+        let names: Vec<_> = t
+            .repo
+            .worktrees()
+            .unwrap()
+            .into_iter()
+            .map(|w| w.name)
+            .collect();
+        assert_eq!(names.len(), 2);
+        assert!(names.contains(&"at-head".to_string()) && names.contains(&"at-rev".to_string()));
+
+        let linked = t.repo.open_worktree("at-rev").unwrap();
+        assert!(linked.is_worktree());
+        assert_eq!(linked.inner.head().unwrap().shorthand(), Some("at-rev"));
+    }
+
+    #[test]
+    fn add_checks_out_an_existing_branch() {
+        let t = TestRepo::new();
+        let first = t.commit("file.txt", "one\n", "first");
+        t.commit("file.txt", "two\n", "second");
+        t.git()
+            .branch("feature", &t.git().find_commit(first).unwrap(), false)
+            .unwrap();
+
+        let path = t.sibling("feature-tree");
+        t.repo
+            .add_worktree(
+                "feature-tree",
+                path.to_str().unwrap(),
+                Some("feature"),
+                false,
+            )
+            .unwrap();
+        assert_eq!(branch_target(&t, "feature-tree"), None);
+        assert_eq!(fs::read_to_string(path.join("file.txt")).unwrap(), "one\n");
+    }
+
+    #[test]
+    fn failed_add_removes_the_new_branch() {
+        let t = TestRepo::new();
+        t.commit("file.txt", "one\n", "first");
+        t.commit("file.txt", "two\n", "second");
+        let path = t.sibling("occupied");
+        fs::create_dir(&path).unwrap();
+        fs::write(path.join("keep.txt"), "keep\n").unwrap();
+
+        let added = t
+            .repo
+            .add_worktree("occupied", path.to_str().unwrap(), Some("HEAD~1"), false);
+        assert!(added.is_err());
+        assert_eq!(branch_target(&t, "occupied"), None);
+        assert!(t.repo.worktrees().unwrap().is_empty());
+        assert!(t
+            .repo
+            .add_worktree(
+                "bad",
+                t.sibling("bad").to_str().unwrap(),
+                Some("no-such-rev"),
+                false
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn lock_and_prune() {
+        let t = TestRepo::new();
+        t.commit("file.txt", "one\n", "first");
+        let path = t.sibling("linked");
+        let path_str = path.to_str().unwrap();
+        t.repo.add_worktree("linked", path_str, None, true).unwrap();
+
+        let worktree = &t.repo.worktrees().unwrap()[0];
+        assert!(worktree.locked);
+        t.repo.unlock_worktree("linked").unwrap();
+        t.repo
+            .lock_worktree("linked", Some("on a removable disk"))
+            .unwrap();
+        let worktree = &t.repo.worktrees().unwrap()[0];
+        assert_eq!(worktree.lock_reason.as_deref(), Some("on a removable disk"));
+        t.repo.unlock_worktree("linked").unwrap();
+        assert!(!t.repo.worktrees().unwrap()[0].locked);
+
+        // A valid worktree is only pruned when forced.
+        assert!(t.repo.prune_worktree("linked", true, false).is_err());
+        fs::remove_dir_all(&path).unwrap();
+        assert!(!t.repo.worktrees().unwrap()[0].is_valid);
+        t.repo.prune_worktree("linked", true, false).unwrap();
+        assert!(t.repo.worktrees().unwrap().is_empty());
+        assert!(t.repo.prune_worktree("linked", true, false).is_err());
+
+        t.repo
+            .add_worktree("forced", t.sibling("forced").to_str().unwrap(), None, true)
+            .unwrap();
+        t.repo.prune_worktree("forced", true, true).unwrap();
+        assert!(t.repo.worktrees().unwrap().is_empty());
+        assert!(!t.sibling("forced").exists());
+    }
+}
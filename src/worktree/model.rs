@@ -0,0 +1,27 @@
+use crate::utils::py_model;
+use pyo3::prelude::*;
+
+/// A linked worktree of the repository, as listed by `git worktree list`.
+#[pyclass(module = "rustygit")]
+#[derive(Clone, Debug)]
+pub struct Worktree {
+    #[pyo3(get)]
+    pub name: String,
+    #[pyo3(get)]
+    pub path: String,
+    #[pyo3(get)]
+    pub locked: bool,
+    #[pyo3(get)]
+    pub lock_reason: Option<String>,
+    /// Whether the worktree's directory and git files are still in place;
+    /// invalid worktrees can be pruned
+    #[pyo3(get)]
+    pub is_valid: bool,
+}
+
+py_model!(
+    Worktree,
+    fields: [name, path, locked, lock_reason, is_valid],
+    key: [name, path],
+    repr: [name, path, locked],
+);